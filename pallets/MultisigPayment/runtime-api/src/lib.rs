@@ -0,0 +1,31 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Runtime API to list the settled payments of an account, as payer or as payee, page by page.
+// `Record` is the pallet's CallExecuted type of the runtime.
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+// A page of history records, oldest first. `next` is the sequence number to pass as `start` for
+// the following page, None when there is nothing left.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct HistoryPage<Record> {
+	pub records: Vec<Record>,
+	pub next: Option<u32>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait VanePaymentApi<AccountId, Record>
+	where
+		AccountId: Codec,
+		Record: Codec,
+	{
+		// Payments `who` made, starting at sequence number `start`
+		fn payer_history(who: AccountId, start: u32, limit: u32) -> HistoryPage<Record>;
+
+		// Payments `who` received, starting at sequence number `start`
+		fn payee_history(who: AccountId, start: u32, limit: u32) -> HistoryPage<Record>;
+	}
+}
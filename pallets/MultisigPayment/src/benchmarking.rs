@@ -1,9 +1,727 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-multisig-payment
+//!
+//! Every call is measured in its worst case: the legal team picked from a full resolver set, the
+//! expiry and fault decay slots of the block one entry away from full, a payer whose faults
+//! reserve a penalty deposit, bar the account and slash a penalty, settlements paying MaxPayees
+//! payees of a split payment and MaxMilestones milestones.
 
 use super::*;
+use crate::helper::{
+	Confirm, DisputeVerdict, FaultRole, PaymentAsset, PaymentStatus, ResolverChoice,
+	ResolverInfo, RevertReasons,
+};
+#[allow(unused)]
+use crate::Pallet as VanePayment;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	traits::{Hash, One, Saturating},
+	Perbill,
+};
+use sp_std::vec::Vec;
 
+const SEED: u32 = 0;
 
-use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_system::RawOrigin;
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(
+		&who,
+		T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+	);
+	who
+}
+
+// Funds a payer for MaxExpiringPerBlock split payments with their dispute deposits
+fn well_funded_account<T: Config>() -> T::AccountId {
+	let who: T::AccountId = account("payer", 0, SEED);
+	T::Currency::make_free_balance_be(
+		&who,
+		T::Currency::minimum_balance().saturating_mul(100_000_000u32.into()),
+	);
+	who
+}
+
+fn payment_amount<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance().saturating_mul(1_000u32.into())
+}
+
+// Admits `n` legal team accounts, each bonded and charging MaxResolverFee
+fn fill_resolvers<T: Config>(n: u32) {
+	let resolvers: Vec<T::AccountId> = (0..n).map(|i| funded_account::<T>("resolver", i)).collect();
+	for resolver in resolvers.iter() {
+		let _ = T::Currency::reserve(resolver, T::ResolverBond::get());
+		Resolvers::<T>::insert(
+			resolver,
+			ResolverInfo { bond: T::ResolverBond::get(), fee: T::MaxResolverFee::get() },
+		);
+	}
+	ResolverSigner::<T>::put(
+		BoundedVec::try_from(resolvers).expect("n is at most MaxResolvers; qed"),
+	);
+}
+
+// Leaves a single free slot in the expiry list of payments opened in this block
+fn fill_expiry_slots<T: Config>() {
+	let expiry =
+		frame_system::Pallet::<T>::block_number().saturating_add(T::PaymentTimeout::get());
+	let payments: Vec<PaymentId> = (0..T::MaxExpiringPerBlock::get().saturating_sub(1))
+		.map(|i| PaymentId::MAX - i)
+		.collect();
+	ExpiringPayments::<T>::insert(
+		expiry,
+		BoundedVec::try_from(payments).expect("less than MaxExpiringPerBlock; qed"),
+	);
+}
+
+// Fills the decay list of faults recorded in this block and leaves a single free slot in the
+// next block's one it spills into
+fn fill_fault_slots<T: Config>() {
+	let decay_at =
+		frame_system::Pallet::<T>::block_number().saturating_add(T::FaultWindow::get());
+	let max = T::MaxFaultsPerBlock::get();
+	let spill_at = decay_at.saturating_add(One::one());
+	for (at, len) in [(decay_at, max), (spill_at, max.saturating_sub(1))] {
+		let faults: Vec<(T::AccountId, FaultRole)> =
+			(0..len).map(|i| (account("faulty", i, SEED), FaultRole::Payer)).collect();
+		FaultDecay::<T>::insert(
+			at,
+			BoundedVec::try_from(faults).expect("at most MaxFaultsPerBlock; qed"),
+		);
+	}
+}
+
+// Opens a payment from `payer` to `payee` and returns its id
+fn open_payment<T: Config>(
+	payer: &T::AccountId,
+	payee: &T::AccountId,
+	resolver: ResolverChoice,
+) -> Result<PaymentId, &'static str> {
+	let payment_id = NextPaymentId::<T>::get();
+	VanePayment::<T>::vane_pay(
+		RawOrigin::Signed(payer.clone()).into(),
+		Some(payee.clone()),
+		None,
+		payment_amount::<T>(),
+		PaymentAsset::Native,
+		resolver,
+	)?;
+	Ok(payment_id)
+}
+
+// `n` payees, each with a share of payment_amount
+fn split_payees<T: Config>(n: u32) -> BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPayees> {
+	let payees: Vec<(T::AccountId, BalanceOf<T>)> =
+		(0..n).map(|i| (funded_account::<T>("payee", i), payment_amount::<T>())).collect();
+	BoundedVec::try_from(payees).expect("n is at most MaxPayees; qed")
+}
+
+// `n` milestones of payment_amount each
+fn milestone_schedule<T: Config>(n: u32) -> BoundedVec<(BalanceOf<T>, T::Hash), T::MaxMilestones> {
+	let schedule: Vec<(BalanceOf<T>, T::Hash)> =
+		(0..n).map(|i| (payment_amount::<T>(), T::Hashing::hash_of(&i))).collect();
+	BoundedVec::try_from(schedule).expect("n is at most MaxMilestones; qed")
+}
+
+// A preimage of MaxPreimageLength bytes
+fn htlc_preimage<T: Config>() -> BoundedVec<u8, T::MaxPreimageLength> {
+	BoundedVec::try_from(sp_std::vec![7u8; T::MaxPreimageLength::get() as usize])
+		.expect("MaxPreimageLength bytes; qed")
+}
+
+// Opens a payment from `payer` to `payee` locked to the hash of htlc_preimage for PaymentTimeout
+// blocks
+fn open_htlc_payment<T: Config>(
+	payer: &T::AccountId,
+	payee: &T::AccountId,
+) -> Result<PaymentId, &'static str> {
+	let payment_id = NextPaymentId::<T>::get();
+	VanePayment::<T>::vane_pay_htlc(
+		RawOrigin::Signed(payer.clone()).into(),
+		payee.clone(),
+		payment_amount::<T>(),
+		PaymentAsset::Native,
+		blake2_256(&htlc_preimage::<T>()),
+		T::PaymentTimeout::get(),
+	)?;
+	Ok(payment_id)
+}
+
+// Opens a legal team payment from `payer` split between MaxPayees payees, disputes it and has
+// its legal team rule on it
+fn open_ruling<T: Config>(payer: &T::AccountId) -> Result<PaymentId, &'static str> {
+	fill_resolvers::<T>(T::MaxResolvers::get());
+	let payment_id = open_split_payment::<T>(payer, ResolverChoice::LegalTeam, false)?;
+	let resolver = AllowedSigners::<T>::get(payment_id)
+		.and_then(|signers| signers.get_legal_account().cloned())
+		.ok_or("payment has a legal team")?;
+
+	VanePayment::<T>::open_dispute(
+		RawOrigin::Signed(payer.clone()).into(),
+		payment_id,
+		Default::default(),
+		T::Hashing::hash_of(&0u32),
+	)?;
+	VanePayment::<T>::resolve_dispute(
+		RawOrigin::Signed(resolver).into(),
+		payment_id,
+		DisputeVerdict::Split(Perbill::from_percent(50)),
+	)?;
+	Ok(payment_id)
+}
+
+// Opens a payment from `payer` split between MaxPayees payees, all of them confirmed when
+// `confirm` is set
+fn open_split_payment<T: Config>(
+	payer: &T::AccountId,
+	resolver: ResolverChoice,
+	confirm: bool,
+) -> Result<PaymentId, &'static str> {
+	open_split_payment_to::<T>(payer, resolver, T::MaxPayees::get(), confirm)
+}
+
+// Same as open_split_payment with `payees` payees
+fn open_split_payment_to<T: Config>(
+	payer: &T::AccountId,
+	resolver: ResolverChoice,
+	payees: u32,
+	confirm: bool,
+) -> Result<PaymentId, &'static str> {
+	let payees = split_payees::<T>(payees);
+	let payment_id = NextPaymentId::<T>::get();
+	VanePayment::<T>::vane_pay_split(
+		RawOrigin::Signed(payer.clone()).into(),
+		payees.clone(),
+		resolver,
+	)?;
+
+	if confirm {
+		for (payee, _) in payees {
+			VanePayment::<T>::confirm_pay(
+				RawOrigin::Signed(payee).into(),
+				Confirm::Payee,
+				payment_id,
+			)?;
+		}
+	}
+	Ok(payment_id)
+}
+
+benchmarks! {
+	vane_pay {
+		fill_resolvers::<T>(T::MaxResolvers::get());
+		fill_expiry_slots::<T>();
+
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		// Enough faults for the payer to reserve a penalty deposit
+		RevertedTxnPayer::<T>::insert(&payer, T::DepositThreshold::get());
+
+		let payment_id = NextPaymentId::<T>::get();
+		let amount = payment_amount::<T>();
+		let asset = PaymentAsset::Native;
+	}: _(RawOrigin::Signed(payer), Some(payee), None, amount, asset, ResolverChoice::LegalTeam)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Created));
+	}
+
+	vane_pay_split {
+		let p in 1 .. T::MaxPayees::get();
+
+		fill_resolvers::<T>(T::MaxResolvers::get());
+		fill_expiry_slots::<T>();
+
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payees = split_payees::<T>(p);
+		RevertedTxnPayer::<T>::insert(&payer, T::DepositThreshold::get());
+
+		let payment_id = NextPaymentId::<T>::get();
+	}: _(RawOrigin::Signed(payer), payees, ResolverChoice::LegalTeam)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Created));
+	}
+
+	vane_pay_milestones {
+		let m in 1 .. T::MaxMilestones::get();
+
+		fill_resolvers::<T>(T::MaxResolvers::get());
+		fill_expiry_slots::<T>();
+
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		RevertedTxnPayer::<T>::insert(&payer, T::DepositThreshold::get());
+
+		let schedule = milestone_schedule::<T>(m);
+		let payment_id = NextPaymentId::<T>::get();
+	}: _(RawOrigin::Signed(payer), payee, schedule, ResolverChoice::LegalTeam)
+	verify {
+		assert_eq!(Milestones::<T>::get(payment_id).map(|m| m.len() as u32), Some(m));
+	}
+
+	vane_pay_htlc {
+		fill_expiry_slots::<T>();
+
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		RevertedTxnPayer::<T>::insert(&payer, T::DepositThreshold::get());
+
+		let payment_id = NextPaymentId::<T>::get();
+		let amount = payment_amount::<T>();
+		let hashlock = blake2_256(&htlc_preimage::<T>());
+		let timelock = T::PaymentTimeout::get();
+	}: _(RawOrigin::Signed(payer), payee, amount, PaymentAsset::Native, hashlock, timelock)
+	verify {
+		assert!(HashLocks::<T>::contains_key(payment_id));
+	}
+
+	// A preimage of MaxPreimageLength bytes
+	claim_htlc {
+		let payer = funded_account::<T>("payer", 0);
+		let payee: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&payee, T::Currency::minimum_balance());
+
+		let payment_id = open_htlc_payment::<T>(&payer, &payee)?;
+		let preimage = htlc_preimage::<T>();
+	}: _(RawOrigin::Signed(payee), payment_id, preimage)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Settled));
+	}
+
+	reclaim_htlc {
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		RevertedTxnPayer::<T>::insert(&payer, T::DepositThreshold::get());
+
+		let payment_id = open_htlc_payment::<T>(&payer, &payee)?;
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number().saturating_add(T::PaymentTimeout::get()),
+		);
+	}: _(RawOrigin::Signed(payer), payment_id)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Expired));
+	}
+
+	claim_payment {
+		let payer = funded_account::<T>("payer", 0);
+		let claimant: T::AccountId = whitelisted_caller();
+		let secret = [7u8; 32];
+		let commitment = (secret, &claimant).using_encoded(blake2_256);
+
+		let payment_id = NextPaymentId::<T>::get();
+		VanePayment::<T>::vane_pay(
+			RawOrigin::Signed(payer).into(),
+			None,
+			Some(commitment),
+			payment_amount::<T>(),
+			PaymentAsset::Native,
+			ResolverChoice::None,
+		)?;
+	}: _(RawOrigin::Signed(claimant), payment_id, secret)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Settled));
+	}
+
+	// The payer's confirmation of the last of MaxMilestones milestones, which settles the payment
+	confirm_milestone {
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		let max = T::MaxMilestones::get();
+		let payment_id = NextPaymentId::<T>::get();
+		VanePayment::<T>::vane_pay_milestones(
+			RawOrigin::Signed(payer.clone()).into(),
+			payee.clone(),
+			milestone_schedule::<T>(max),
+			ResolverChoice::None,
+		)?;
+
+		for index in 0..max {
+			VanePayment::<T>::confirm_milestone(
+				RawOrigin::Signed(payee.clone()).into(),
+				payment_id,
+				index,
+			)?;
+			if index + 1 < max {
+				VanePayment::<T>::confirm_milestone(
+					RawOrigin::Signed(payer.clone()).into(),
+					payment_id,
+					index,
+				)?;
+			}
+		}
+	}: _(RawOrigin::Signed(payer), payment_id, max - 1)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Settled));
+	}
+
+	// The payer's confirmation which settles a payment split between MaxPayees payees
+	confirm_pay {
+		let p in 1 .. T::MaxPayees::get();
+
+		fill_resolvers::<T>(T::MaxResolvers::get());
+
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payment_id = open_split_payment_to::<T>(&payer, ResolverChoice::LegalTeam, p, true)?;
+	}: _(RawOrigin::Signed(payer), Confirm::Payer, payment_id)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Settled));
+	}
+
+	// The payee agreeing to a ChangeOfDecision revert, recording a fault which applies every
+	// punishment
+	revert_fund {
+		fill_fault_slots::<T>();
+
+		let payer = funded_account::<T>("payer", 0);
+		let payee: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&payee, T::Currency::minimum_balance());
+		RevertedTxnPayer::<T>::insert(&payer, T::SlashThreshold::get());
+
+		let payment_id = open_payment::<T>(&payer, &payee, ResolverChoice::None)?;
+		VanePayment::<T>::revert_fund(
+			RawOrigin::Signed(payer.clone()).into(),
+			payment_id,
+			RevertReasons::ChangeOfDecision,
+		)?;
+	}: _(RawOrigin::Signed(payee), payment_id, RevertReasons::ChangeOfDecision)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Refunded));
+		assert!(BarredUntil::<T>::contains_key(&payer));
+	}
+
+	// The payer's penalty deposit is unreserved along with the refund
+	cancel_payment {
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		RevertedTxnPayer::<T>::insert(&payer, T::DepositThreshold::get());
+
+		let payment_id = open_payment::<T>(&payer, &payee, ResolverChoice::None)?;
+	}: _(RawOrigin::Signed(payer), payment_id)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Cancelled));
+	}
+
+	// A reason code of MaxReasonLength bytes
+	reject_payment {
+		let payer = funded_account::<T>("payer", 0);
+		let payee: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&payee, T::Currency::minimum_balance());
+		RevertedTxnPayer::<T>::insert(&payer, T::DepositThreshold::get());
+
+		let payment_id = open_payment::<T>(&payer, &payee, ResolverChoice::None)?;
+		let reason: BoundedVec<u8, T::MaxReasonLength> =
+			BoundedVec::try_from(sp_std::vec![0u8; T::MaxReasonLength::get() as usize])
+				.expect("MaxReasonLength bytes; qed");
+	}: _(RawOrigin::Signed(payee), payment_id, Some(reason))
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Refunded));
+	}
+
+	propose_refund {
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		let payment_id = open_payment::<T>(&payer, &payee, ResolverChoice::None)?;
+	}: _(RawOrigin::Signed(payer), payment_id, Perbill::from_percent(50))
+	verify {
+		assert!(RefundProposals::<T>::contains_key(payment_id));
+	}
+
+	// The payee accepting a partial refund, split payments cannot be partially refunded
+	accept_refund {
+		let payer = funded_account::<T>("payer", 0);
+		let payee: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&payee, T::Currency::minimum_balance());
+		let payment_id = open_payment::<T>(&payer, &payee, ResolverChoice::None)?;
+		VanePayment::<T>::propose_refund(
+			RawOrigin::Signed(payer).into(),
+			payment_id,
+			Perbill::from_percent(50),
+		)?;
+	}: _(RawOrigin::Signed(payee), payment_id, Perbill::from_percent(50))
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Settled));
+	}
+
+	release_fund {
+		fill_resolvers::<T>(1);
+
+		let resolver: T::AccountId = account("resolver", 0, SEED);
+		let payer = funded_account::<T>("payer", 0);
+
+		let payment_id = open_split_payment::<T>(&payer, ResolverChoice::LegalTeam, false)?;
+		VanePayment::<T>::open_dispute(
+			RawOrigin::Signed(payer).into(),
+			payment_id,
+			Default::default(),
+			T::Hashing::hash_of(&0u32),
+		)?;
+	}: _(RawOrigin::Signed(resolver), payment_id)
+	verify {
+		assert!(Rulings::<T>::contains_key(payment_id));
+	}
+
+	add_resolver {
+		let max = T::MaxResolvers::get();
+		fill_resolvers::<T>(max.saturating_sub(1));
+		let new_resolver: T::AccountId = account("resolver", max, SEED);
+		let origin = T::ResolverOrigin::successful_origin();
+	}: _<T::Origin>(origin, new_resolver.clone())
+	verify {
+		assert!(Resolvers::<T>::contains_key(&new_resolver));
+	}
+
+	bond_resolver {
+		fill_resolvers::<T>(T::MaxResolvers::get().saturating_sub(1));
+		let resolver: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&resolver,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		Resolvers::<T>::insert(&resolver, ResolverInfo::new());
+	}: _(RawOrigin::Signed(resolver.clone()), T::MaxResolverFee::get())
+	verify {
+		assert!(ResolverSigner::<T>::get().contains(&resolver));
+	}
+
+	remove_resolver {
+		let max = T::MaxResolvers::get();
+		fill_resolvers::<T>(max);
+		let last: T::AccountId = account("resolver", max.saturating_sub(1), SEED);
+		let origin = T::ResolverOrigin::successful_origin();
+	}: _<T::Origin>(origin, last.clone())
+	verify {
+		assert!(!ResolverSigner::<T>::get().contains(&last));
+		assert!(!Resolvers::<T>::contains_key(&last));
+	}
+
+	// A reason code of MaxReasonLength bytes, the legal team getting a ruling deadline
+	open_dispute {
+		fill_resolvers::<T>(T::MaxResolvers::get());
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payee = funded_account::<T>("payee", 0);
+		let payment_id = open_payment::<T>(&payer, &payee, ResolverChoice::LegalTeam)?;
+		let reason: BoundedVec<u8, T::MaxReasonLength> =
+			BoundedVec::try_from(sp_std::vec![0u8; T::MaxReasonLength::get() as usize])
+				.expect("MaxReasonLength bytes; qed");
+	}: _(RawOrigin::Signed(payer), payment_id, reason, T::Hashing::hash_of(&0u32))
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Disputed));
+	}
+
+	// The last evidence hash MaxEvidence allows
+	add_evidence {
+		let payer = funded_account::<T>("payer", 0);
+		let payee: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payee,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payment_id = open_payment::<T>(&payer, &payee, ResolverChoice::Governance)?;
+		VanePayment::<T>::open_dispute(
+			RawOrigin::Signed(payer.clone()).into(),
+			payment_id,
+			Default::default(),
+			T::Hashing::hash_of(&0u32),
+		)?;
+		for i in 1..T::MaxEvidence::get().saturating_sub(1) {
+			VanePayment::<T>::add_evidence(
+				RawOrigin::Signed(payer.clone()).into(),
+				payment_id,
+				T::Hashing::hash_of(&i),
+			)?;
+		}
+		let evidence_hash = T::Hashing::hash_of(&T::MaxEvidence::get());
+	}: _(RawOrigin::Signed(payee), payment_id, evidence_hash)
+	verify {
+		let dispute = Disputes::<T>::get(payment_id).expect("dispute is open; qed");
+		assert_eq!(dispute.evidence.len() as u32, T::MaxEvidence::get());
+	}
+
+	// Governance upholding an appealed ruling split between the payer and MaxPayees payees, the
+	// legal team being paid its fee
+	resolve_dispute {
+		let payer = funded_account::<T>("payer", 0);
+		let payment_id = open_ruling::<T>(&payer)?;
+		VanePayment::<T>::appeal_ruling(RawOrigin::Signed(payer).into(), payment_id)?;
+		let origin = T::DisputeOrigin::successful_origin();
+	}: _<T::Origin>(origin, payment_id, DisputeVerdict::Split(Perbill::from_percent(50)))
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Settled));
+	}
+
+	appeal_ruling {
+		let payer: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(
+			&payer,
+			T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+		);
+		let payment_id = open_ruling::<T>(&payer)?;
+	}: _(RawOrigin::Signed(payer), payment_id)
+	verify {
+		assert!(Rulings::<T>::get(payment_id).map_or(false, |ruling| ruling.appealed));
+	}
+
+	// The ruling split between the payer and MaxPayees payees, the legal team being paid its fee
+	execute_ruling {
+		let payer = funded_account::<T>("payer", 0);
+		let payment_id = open_ruling::<T>(&payer)?;
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number().saturating_add(T::AppealPeriod::get()),
+		);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), payment_id)
+	verify {
+		assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Settled));
+	}
+
+	// `n` split payments expiring in the block, none of their MaxPayees payees confirmed so every
+	// one of them gets a fault
+	refund_expired_payments {
+		let n in 1 .. T::MaxExpiringPerBlock::get();
+
+		let payer = well_funded_account::<T>();
+		let payments = (0..n)
+			.map(|_| open_split_payment::<T>(&payer, ResolverChoice::None, false))
+			.collect::<Result<Vec<_>, _>>()?;
+		let expiry =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::PaymentTimeout::get());
+		frame_system::Pallet::<T>::set_block_number(expiry);
+	}: {
+		VanePayment::<T>::refund_expired_payments(expiry);
+	}
+	verify {
+		for payment_id in payments {
+			assert_eq!(PaymentStatuses::<T>::get(payment_id), Some(PaymentStatus::Expired));
+		}
+	}
+
+	// `n` faults recorded FaultWindow blocks ago
+	decay_faults {
+		let n in 1 .. T::MaxFaultsPerBlock::get();
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let faulty: Vec<T::AccountId> = (0..n).map(|i| account("faulty", i, SEED)).collect();
+		for who in faulty.iter() {
+			RevertedTxnPayer::<T>::insert(who, 1);
+		}
+		let faults: Vec<(T::AccountId, FaultRole)> =
+			faulty.iter().cloned().map(|who| (who, FaultRole::Payer)).collect();
+		FaultDecay::<T>::insert(
+			now,
+			BoundedVec::try_from(faults).expect("n is at most MaxFaultsPerBlock; qed"),
+		);
+	}: {
+		VanePayment::<T>::decay_faults(now);
+	}
+	verify {
+		for who in faulty {
+			assert_eq!(RevertedTxnPayer::<T>::get(who), 0);
+		}
+	}
+
+	// `n` disputes on split payments whose legal team did not rule in time, each handed to the
+	// next legal team of a full resolver set
+	replace_late_resolvers {
+		let n in 1 .. T::MaxExpiringPerBlock::get();
+
+		fill_resolvers::<T>(T::MaxResolvers::get());
+		let payer = well_funded_account::<T>();
+		let mut payments = Vec::new();
+		for _ in 0..n {
+			let payment_id = open_split_payment::<T>(&payer, ResolverChoice::LegalTeam, false)?;
+			VanePayment::<T>::open_dispute(
+				RawOrigin::Signed(payer.clone()).into(),
+				payment_id,
+				Default::default(),
+				T::Hashing::hash_of(&payment_id),
+			)?;
+			payments.push(payment_id);
+		}
+		let deadline =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::RulingPeriod::get());
+		frame_system::Pallet::<T>::set_block_number(deadline);
+	}: {
+		VanePayment::<T>::replace_late_resolvers(deadline);
+	}
+	verify {
+		let next_deadline = deadline.saturating_add(T::RulingPeriod::get());
+		for payment_id in payments {
+			assert_eq!(
+				DisputeResolvers::<T>::get(payment_id).map(|(_, deadline)| deadline),
+				Some(next_deadline)
+			);
+		}
+	}
+
+	// `n` payments closed StatusRetention blocks ago, each rejected with a reason code
+	prune_closed_payments {
+		let n in 1 .. T::MaxClosedPerBlock::get();
 
+		let now = frame_system::Pallet::<T>::block_number();
+		let payments: Vec<PaymentId> = (0..n as PaymentId).collect();
+		for payment_id in payments.iter() {
+			PaymentStatuses::<T>::insert(payment_id, PaymentStatus::Refunded);
+			RejectionReasons::<T>::insert(
+				payment_id,
+				BoundedVec::try_from(sp_std::vec![0u8; T::MaxReasonLength::get() as usize])
+					.expect("the reason is MaxReasonLength long; qed"),
+			);
+			ClosedPayments::<T>::insert(now, payment_id, ());
+		}
+		NextPrunedBlock::<T>::put(now);
+	}: {
+		VanePayment::<T>::prune_closed_payments(now);
+	}
+	verify {
+		for payment_id in payments {
+			assert_eq!(PaymentStatuses::<T>::get(payment_id), None);
+			assert_eq!(RejectionReasons::<T>::get(payment_id), None);
+		}
+	}
 
+	impl_benchmark_test_suite!(VanePayment, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -15,7 +15,7 @@ use codec::{Decode, Encode};
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
 use sp_runtime::{traits::TrailingZeroInput, MultiAddress};
-use sp_std::mem::drop;
+use sp_std::{mem::drop, vec::Vec};
 
 pub use utils::*;
 pub mod utils {
@@ -25,13 +25,23 @@ pub mod utils {
 			DispatchErrorWithPostInfo, DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo,
 			PostDispatchInfo, RawOrigin,
 		},
-		traits::{Currency, ExistenceRequirement},
+		storage::{
+			with_transaction, StorageDoubleMap as StorageDoubleMapT, StorageMap as StorageMapT,
+			TransactionOutcome,
+		},
+		traits::{
+			tokens::{fungibles, DepositConsequence},
+			BalanceStatus, Currency, ExistenceRequirement, Imbalance, NamedReservableCurrency,
+			OnUnbalanced, ReservableCurrency, WithdrawReasons,
+		},
 	};
 	use frame_system::{Account, AccountInfo};
 	use sp_io::hashing::blake2_256;
 	use sp_runtime::{
-		traits::{Dispatchable, StaticLookup, TrailingZeroInput, Zero},
-		DispatchError,
+		traits::{
+			CheckedAdd, Dispatchable, One, Saturating, StaticLookup, TrailingZeroInput, Zero,
+		},
+		DispatchError, Perbill,
 	};
 
 	// A struct by which it should be used as a source of signatures.
@@ -41,6 +51,9 @@ pub mod utils {
 		payee: T::AccountId,
 		payer: T::AccountId,
 		resolver: Option<Resolver<T>>,
+		// Payment id the multi_id is salted with, None for escrows opened before storage
+		// version 1 which keep the account they were funded in
+		nonce: Option<PaymentId>,
 	}
 
 
@@ -59,7 +72,7 @@ pub mod utils {
 	}
 
 	// This should be used as a parameter for choosing which Resolving method should take place
-	#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 	pub enum ResolverChoice {
 		LegalTeam,
 		Governance,
@@ -75,22 +88,32 @@ pub mod utils {
 			payer: T::AccountId,
 			resolver: Option<Resolver<T>>,
 		) -> Self {
-			AccountSigners { payee, payer, resolver }
+			AccountSigners { payee, payer, resolver, nonce: None }
+		}
+
+		pub(crate) fn with_nonce(mut self, nonce: Option<PaymentId>) -> Self {
+			self.nonce = nonce;
+			self
 		}
-		pub(super) fn get_payer(&self) -> &T::AccountId {
+
+		pub(crate) fn get_payer(&self) -> &T::AccountId {
 			&self.payer
 		}
 
-		pub(super) fn get_payee(&self) -> &T::AccountId {
+		pub(crate) fn get_payee(&self) -> &T::AccountId {
 			&self.payee
 		}
 
-		pub(super) fn get_resolver(&self) -> &Option<Resolver<T>> {
+		pub(crate) fn get_resolver(&self) -> &Option<Resolver<T>> {
 			&self.resolver
 		}
 
+		pub(crate) fn get_nonce(&self) -> Option<PaymentId> {
+			self.nonce
+		}
+
 		// refer here https://doc.rust-lang.org/stable/book/ch06-01-defining-an-enum.html?highlight=enum#enum-values
-		pub(super) fn get_legal_account(&self) -> Option<&T::AccountId> {
+		pub(crate) fn get_legal_account(&self) -> Option<&T::AccountId> {
 			if let Some(Resolver::LegalTeam(account)) = &self.resolver {
 				Some(account)
 			} else {
@@ -103,8 +126,13 @@ pub mod utils {
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 	#[scale_info(skip_type_params(T))]
 	pub struct CallExecuted<T: Config> {
+		// None for the records migrated from AccountMultiTxns, which predate payment ids and
+		// record neither amount nor fee
+		payment_id: Option<PaymentId>,
 		payer: T::AccountId,
 		payee: T::AccountId,
+		amount: BalanceOf<T>,
+		fee: BalanceOf<T>,
 		allowed_multi_id: T::AccountId,
 		confirmed_multi_id: T::AccountId,
 		proof: T::Hash,
@@ -115,7 +143,32 @@ pub mod utils {
 	where
 		T: Config,
 	{
-		pub(super) fn new(
+		pub(crate) fn new(
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			payee: T::AccountId,
+			amount: BalanceOf<T>,
+			fee: BalanceOf<T>,
+			allowed_multi_id: T::AccountId,
+			confirmed_multi_id: T::AccountId,
+			proof: T::Hash,
+			time: T::BlockNumber,
+		) -> Self {
+			CallExecuted {
+				payment_id: Some(payment_id),
+				payer,
+				payee,
+				amount,
+				fee,
+				allowed_multi_id,
+				confirmed_multi_id,
+				proof,
+				time,
+			}
+		}
+
+		// A record written before payment ids, without payment id and with zero amount and fee
+		pub(crate) fn legacy(
 			payer: T::AccountId,
 			payee: T::AccountId,
 			allowed_multi_id: T::AccountId,
@@ -123,10 +176,123 @@ pub mod utils {
 			proof: T::Hash,
 			time: T::BlockNumber,
 		) -> Self {
-			CallExecuted { payer, payee, allowed_multi_id, confirmed_multi_id, proof, time }
+			CallExecuted {
+				payment_id: None,
+				payer,
+				payee,
+				amount: Zero::zero(),
+				fee: Zero::zero(),
+				allowed_multi_id,
+				confirmed_multi_id,
+				proof,
+				time,
+			}
+		}
+	}
+
+	// A tranche of a milestone payment, released once both the payer and the payee confirmed it
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Milestone<T: Config> {
+		pub amount: BalanceOf<T>,
+		// Hash of the work description both parties agreed on
+		pub description: T::Hash,
+		pub payee_confirmed: bool,
+		pub payer_confirmed: bool,
+		pub released: bool,
+	}
+
+	impl<T> Milestone<T>
+	where
+		T: Config,
+	{
+		pub(super) fn new(amount: BalanceOf<T>, description: T::Hash) -> Self {
+			Milestone {
+				amount,
+				description,
+				payee_confirmed: false,
+				payer_confirmed: false,
+				released: false,
+			}
+		}
+	}
+
+	// Lock of a payment claimed with a preimage instead of being confirmed
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct HashLock<T: Config> {
+		// blake2_256 of the preimage the payee has to reveal
+		pub hashlock: [u8; 32],
+		// First block at which the payee can no longer claim and the payer can reclaim
+		pub expires_at: T::BlockNumber,
+	}
+
+	// A dispute opened on a payment, waiting for the decision of its resolver
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Dispute<T: Config> {
+		pub opened_by: T::AccountId,
+		pub reason: BoundedVec<u8, T::MaxReasonLength>,
+		// Hashes committed by the payer and the payee, the documents themselves stay off-chain
+		// or in pallet_preimage
+		pub evidence: BoundedVec<(T::AccountId, T::Hash), T::MaxEvidence>,
+		// Reserved from opened_by until the dispute is resolved
+		pub deposit: BalanceOf<T>,
+	}
+
+	impl<T> Dispute<T>
+	where
+		T: Config,
+	{
+		pub(crate) fn new(
+			opened_by: T::AccountId,
+			reason: BoundedVec<u8, T::MaxReasonLength>,
+			deposit: BalanceOf<T>,
+		) -> Self {
+			Dispute { opened_by, reason, evidence: Default::default(), deposit }
+		}
+	}
+
+	// Registry entry of a legal team account admitted by ResolverOrigin
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct ResolverInfo<T: Config> {
+		// Reserved from the account, it is chosen as a resolver while this is at least
+		// ResolverBond
+		pub bond: BalanceOf<T>,
+		// Share of the escrow earned on every dispute it resolves
+		pub fee: Perbill,
+	}
+
+	impl<T> ResolverInfo<T>
+	where
+		T: Config,
+	{
+		pub(crate) fn new() -> Self {
+			ResolverInfo { bond: Zero::zero(), fee: Perbill::zero() }
 		}
 	}
 
+	// Verdict of a legal team, executed once its appeal period ended without appeal
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Ruling<T: Config> {
+		pub resolver: T::AccountId,
+		pub verdict: DisputeVerdict,
+		// First block at which the ruling can no longer be appealed and can be executed
+		pub appeal_ends: T::BlockNumber,
+		pub appealed: bool,
+	}
+
+	// Sequence numbers of the oldest kept record and of the next record of an account's history
+	#[derive(
+		Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo,
+	)]
+	pub struct HistoryBounds {
+		pub first: u32,
+		pub next: u32,
+	}
+
 	// Revert Fund reasons enum
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 	pub enum RevertReasons {
@@ -140,11 +306,266 @@ pub mod utils {
 	}
 
 	// Seller's reason to make fund go through when a buyer misbehave
-	#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 	pub enum PayeeReason {
 		PayerMisbehaviour,
 	}
 
+	// Outcome of a governance dispute
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum DisputeVerdict {
+		// Funds go through to the payee, the payer's fault is recorded
+		ReleaseToPayee,
+		// Funds are refunded to the payer, the payee's fault is recorded
+		RefundPayer,
+		// The payee gets the given share of the funds and the rest goes back to the payer
+		Split(Perbill),
+	}
+
+	// Lifecycle of a payment, stored in PaymentStatuses for every payment issued by vane_pay
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum PaymentStatus {
+		// Funds are locked, nobody confirmed yet
+		Created,
+		// The payee confirmed, waiting for the payer
+		PayeeConfirmed,
+		// Funds went to the payee
+		Settled,
+		// Funds went back to the payer
+		Refunded,
+		// Waiting for a dispute decision, the normal flow is frozen
+		Disputed,
+		// Refunded by on_initialize after PaymentTimeout
+		Expired,
+		// Withdrawn by the payer before the payee confirmed
+		Cancelled,
+	}
+
+	impl PaymentStatus {
+		// Settled, Refunded, Expired and Cancelled are final
+		pub fn is_final(&self) -> bool {
+			use PaymentStatus::*;
+			matches!(self, Settled | Refunded | Expired | Cancelled)
+		}
+
+		pub fn can_transition_to(&self, next: &PaymentStatus) -> bool {
+			use PaymentStatus::*;
+			match (self, next) {
+				(Created, PayeeConfirmed | Settled | Refunded | Disputed | Expired | Cancelled) =>
+					true,
+				(PayeeConfirmed, Settled | Refunded | Disputed | Expired) => true,
+				(Disputed, Settled | Refunded) => true,
+				_ => false,
+			}
+		}
+	}
+
+	// Asset a payment is made in
+	#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum PaymentAsset<AssetId> {
+		// The native currency, held through Config::Escrow
+		Native,
+		// An asset of Config::Assets, held in the multi_id
+		Asset(AssetId),
+	}
+
+	impl<AssetId> Default for PaymentAsset<AssetId> {
+		fn default() -> Self {
+			PaymentAsset::Native
+		}
+	}
+
+	// Side of the payment an account was on when a fault was recorded against it
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum FaultRole {
+		// Recorded in RevertedTxnPayer
+		Payer,
+		// Recorded in RevertedTxnPayee
+		Payee,
+	}
+
+	// Where the payer's funds are held while a payment is open. Selected with Config::Escrow.
+	pub trait EscrowBackend<T: Config> {
+		// Takes `amount` from the payer and holds it for the payment
+		fn lock(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult;
+
+		// Sends `amount` of the held funds to `to`. `close` is true when nothing of the payment
+		// stays held afterwards.
+		fn release(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+			close: bool,
+		) -> DispatchResult;
+
+		// Takes `amount` out of the held funds as an imbalance, used for the protocol fee. The
+		// rest of the payment stays held.
+		fn withdraw(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<NegativeImbalanceOf<T>, DispatchError>;
+	}
+
+	// Funds are transferred to the keyless multi_id account derived from AccountSigners
+	pub struct MultiIdEscrow;
+
+	impl<T: Config> EscrowBackend<T> for MultiIdEscrow {
+		fn lock(
+			_payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			Pallet::<T>::create_multi_account(multi_id.clone())?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Pallet::<T>::deposit_event(Event::MultiAccountCreated {
+				account_id: multi_id.clone(),
+				timestamp: time,
+			});
+
+			// Transfer balance from Payer to Multi_Id
+			T::Currency::transfer(payer, multi_id, amount, ExistenceRequirement::KeepAlive)?;
+			EscrowedTotal::<T>::mutate(multi_id, |total| *total = total.saturating_add(amount));
+
+			Ok(())
+		}
+
+		// When it is the last open payment of the multi_id, whatever else it holds (stray
+		// deposits or dust) is first swept back to the payer, so the account is closed empty and
+		// nothing is burnt. Partial releases must keep the multi_id alive.
+		fn release(
+			_payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+			close: bool,
+		) -> DispatchResult {
+			let escrowed = EscrowedTotal::<T>::get(multi_id);
+			ensure!(escrowed >= amount, Error::<T>::EscrowAccountingFailed);
+			let remaining = escrowed.saturating_sub(amount);
+
+			if !close {
+				T::Currency::transfer(multi_id, to, amount, ExistenceRequirement::KeepAlive)?;
+				EscrowedTotal::<T>::insert(multi_id, remaining);
+				return Ok(())
+			}
+
+			if remaining.is_zero() {
+				let dust = T::Currency::free_balance(multi_id).saturating_sub(amount);
+				if !dust.is_zero() {
+					// The locked amount keeps the multi_id alive during the sweep
+					T::Currency::transfer(multi_id, payer, dust, ExistenceRequirement::KeepAlive)?;
+
+					let time = <frame_system::Pallet<T>>::block_number();
+					Pallet::<T>::deposit_event(Event::DustSwept {
+						multi_id: multi_id.clone(),
+						to_account: payer.clone(),
+						amount: dust,
+						timestamp: time,
+					});
+				}
+				EscrowedTotal::<T>::remove(multi_id);
+			} else {
+				EscrowedTotal::<T>::insert(multi_id, remaining);
+			}
+
+			T::Currency::transfer(multi_id, to, amount, ExistenceRequirement::AllowDeath)
+		}
+
+		fn withdraw(
+			_payment_id: PaymentId,
+			_payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<NegativeImbalanceOf<T>, DispatchError> {
+			let escrowed = EscrowedTotal::<T>::get(multi_id);
+			ensure!(escrowed >= amount, Error::<T>::EscrowAccountingFailed);
+
+			let imbalance = T::Currency::withdraw(
+				multi_id,
+				amount,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			EscrowedTotal::<T>::insert(multi_id, escrowed.saturating_sub(amount));
+
+			Ok(imbalance)
+		}
+	}
+
+	// Funds stay in the payer's account under a named reserve per payment id and are
+	// repatriated to the receiver on release. The multi_id is still derived for signatures but
+	// never holds funds.
+	pub struct ReserveEscrow;
+
+	impl ReserveEscrow {
+		// b"vane" followed by the little endian payment id
+		pub fn reserve_id(payment_id: PaymentId) -> [u8; 8] {
+			let mut id = *b"vane\0\0\0\0";
+			id[4..].copy_from_slice(&payment_id.to_le_bytes());
+			id
+		}
+	}
+
+	impl<T: Config> EscrowBackend<T> for ReserveEscrow
+	where
+		T::Currency: NamedReservableCurrency<T::AccountId, ReserveIdentifier = [u8; 8]>,
+	{
+		fn lock(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			_multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::Currency::reserve_named(&Self::reserve_id(payment_id), payer, amount)
+		}
+
+		fn release(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			_multi_id: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+			_close: bool,
+		) -> DispatchResult {
+			let id = Self::reserve_id(payment_id);
+
+			let not_moved = if to == payer {
+				T::Currency::unreserve_named(&id, payer, amount)
+			} else {
+				T::Currency::repatriate_reserved_named(&id, payer, to, amount, BalanceStatus::Free)?
+			};
+			ensure!(not_moved.is_zero(), Error::<T>::EscrowAccountingFailed);
+
+			Ok(())
+		}
+
+		fn withdraw(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			_multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<NegativeImbalanceOf<T>, DispatchError> {
+			let (imbalance, not_slashed) =
+				T::Currency::slash_reserved_named(&Self::reserve_id(payment_id), payer, amount);
+			ensure!(not_slashed.is_zero(), Error::<T>::EscrowAccountingFailed);
+
+			Ok(imbalance)
+		}
+	}
+
 	// Confirmation enum which will be used to confirm the account_ids before dispatching multi-sig
 	// Call
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -163,92 +584,1703 @@ pub mod utils {
 			payer: T::AccountId,
 			payee: T::AccountId,
 			amount: BalanceOf<T>,
-		) -> DispatchResult {
-			let accounts = AccountSigners::<T>::new(payee, payer.clone(), None);
-			let multi_id = Self::derive_multi_id(accounts.clone());
+			asset: PaymentAsset<AssetIdOf<T>>,
+		) -> Result<PaymentId, DispatchError> {
+			let accounts = AccountSigners::<T>::new(payee, payer, None);
+			Self::open_escrow(accounts, amount, asset)
+		}
 
-			AllowedSigners::<T>::insert(&payer, accounts);
-			Self::create_multi_account(multi_id.clone())?;
+		// Opening of a three party multi-sig account, the legal team account is picked in turns
+		// from the approved resolvers
+		pub(crate) fn inner_vane_pay_with_legal_team(
+			payer: T::AccountId,
+			payee: T::AccountId,
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+		) -> Result<PaymentId, DispatchError> {
+			let legal_account = Self::next_legal_team(&[&payer, &payee])?;
+			let accounts =
+				AccountSigners::<T>::new(payee, payer, Some(Resolver::LegalTeam(legal_account)));
+			Self::open_escrow(accounts, amount, asset)
+		}
 
-			let time = <frame_system::Pallet<T>>::block_number();
+		// Opening of a multi-sig account whose disputes are decided by governance
+		pub(crate) fn inner_vane_pay_with_governance(
+			payer: T::AccountId,
+			payee: T::AccountId,
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+		) -> Result<PaymentId, DispatchError> {
+			let accounts = AccountSigners::<T>::new(payee, payer, Some(Resolver::Governance));
+			Self::open_escrow(accounts, amount, asset)
+		}
 
-			Self::deposit_event(Event::MultiAccountCreated {
-				account_id: multi_id.clone(),
-				timestamp: time,
-			});
+		// Opening of a payment shared by several payees, the first payee is used for AccountSigners
+		// and the multi_id
+		pub(crate) fn inner_vane_pay_split(
+			payer: T::AccountId,
+			payees: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPayees>,
+			resolver: ResolverChoice,
+		) -> Result<PaymentId, DispatchError> {
+			let (lead_payee, _) = payees.first().cloned().ok_or(Error::<T>::NoPayees)?;
 
-			// Transfer balance from Payer to Multi_Id
-			T::Currency::transfer(&payer, &multi_id, amount, ExistenceRequirement::KeepAlive)?;
+			let mut amount: BalanceOf<T> = Zero::zero();
+			for (index, (payee, share)) in payees.iter().enumerate() {
+				// Every share is transferred on its own, less its part of the protocol fee, so it
+				// must be able to create the account
+				ensure!(
+					Self::after_protocol_fee(*share) >= T::Currency::minimum_balance(),
+					Error::<T>::AmountBelowMinimum
+				);
+				ensure!(
+					!payees.iter().take(index).any(|(other, _)| other == payee),
+					Error::<T>::DuplicatePayee
+				);
+				amount = amount.checked_add(share).ok_or(Error::<T>::AmountOverflow)?;
+			}
 
-			Self::deposit_event(Event::BalanceTransferredAndLocked {
-				to_multi_id: multi_id,
-				from: payer,
-				timestamp: time,
-			});
+			let mut parties: Vec<&T::AccountId> = payees.iter().map(|(payee, _)| payee).collect();
+			parties.push(&payer);
+			let resolver = Self::resolver_of_choice(resolver, &parties)?;
+			let accounts = AccountSigners::<T>::new(lead_payee, payer, resolver);
+			let payment_id = Self::open_escrow(accounts, amount, PaymentAsset::Native)?;
+			SplitPayees::<T>::insert(payment_id, payees);
 
-			Ok(())
+			Ok(payment_id)
 		}
 
-		// Dispatching Call helper
-		pub(crate) fn dispatch_transfer_call(
-			proof: T::Hash,
+		// Opening of a payment released in milestones, returns its id and the total locked
+		pub(crate) fn inner_vane_pay_milestones(
 			payer: T::AccountId,
 			payee: T::AccountId,
-			allowed_multi_id: T::AccountId,
-			confirmed_multi_id: T::AccountId,
+			schedule: BoundedVec<(BalanceOf<T>, T::Hash), T::MaxMilestones>,
+			resolver: ResolverChoice,
+		) -> Result<(PaymentId, BalanceOf<T>), DispatchError> {
+			ensure!(!schedule.is_empty(), Error::<T>::NoMilestones);
+
+			let mut amount: BalanceOf<T> = Zero::zero();
+			for (milestone_amount, _) in schedule.iter() {
+				// Every milestone is transferred on its own, less the protocol fee, so it must be
+				// able to create the payee's account
+				ensure!(
+					Self::after_protocol_fee(*milestone_amount) >= T::Currency::minimum_balance(),
+					Error::<T>::AmountBelowMinimum
+				);
+				amount = amount.checked_add(milestone_amount).ok_or(Error::<T>::AmountOverflow)?;
+			}
+
+			let milestones: BoundedVec<Milestone<T>, T::MaxMilestones> = schedule
+				.into_iter()
+				.map(|(amount, description)| Milestone::new(amount, description))
+				.collect::<Vec<_>>()
+				.try_into()
+				.map_err(|_| Error::<T>::UnexpectedError)?;
+
+			let resolver = Self::resolver_of_choice(resolver, &[&payer, &payee])?;
+			let accounts = AccountSigners::<T>::new(payee, payer, resolver);
+			let payment_id = Self::open_escrow(accounts, amount, PaymentAsset::Native)?;
+			Milestones::<T>::insert(payment_id, milestones);
+
+			Ok((payment_id, amount))
+		}
+
+		// Opening of a hash locked payment, returns its id and the block its timelock expires at.
+		// The timelock must end before the escrow expires so the payee always gets the full
+		// window to claim.
+		pub(crate) fn inner_vane_pay_htlc(
+			payer: T::AccountId,
+			payee: T::AccountId,
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+			hashlock: [u8; 32],
+			timelock: T::BlockNumber,
+		) -> Result<(PaymentId, T::BlockNumber), DispatchError> {
+			ensure!(
+				!timelock.is_zero() && timelock <= T::PaymentTimeout::get(),
+				Error::<T>::InvalidTimelock
+			);
+
+			let accounts = AccountSigners::<T>::new(payee, payer, None);
+			let payment_id = Self::open_escrow(accounts, amount, asset)?;
+
+			let expires_at = <frame_system::Pallet<T>>::block_number().saturating_add(timelock);
+			HashLocks::<T>::insert(payment_id, HashLock { hashlock, expires_at });
+
+			Ok((payment_id, expires_at))
+		}
+
+		// Settles a hash locked payment to the payee if the preimage matches and the timelock
+		// has not expired
+		pub(crate) fn inner_claim_htlc(
+			payment_id: PaymentId,
+			signers: AccountSigners<T>,
+			preimage: &[u8],
 		) -> DispatchResult {
-			// Store the proof and associated data of call execution
-			// construct transfer call from pallet balances
-			let acc_payee = payee.clone();
-			let payee = <<T as frame_system::Config>::Lookup as StaticLookup>::unlookup(payee);
+			let lock = HashLocks::<T>::get(payment_id).ok_or(Error::<T>::NotHashLocked)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < lock.expires_at,
+				Error::<T>::TimelockExpired
+			);
+			ensure!(blake2_256(preimage) == lock.hashlock, Error::<T>::InvalidPreimage);
 
-			<pallet_balances::Pallet<T, ()>>::transfer_all(
-				RawOrigin::Signed(allowed_multi_id.clone()).into(),
+			let payer = signers.get_payer().clone();
+			let payee = signers.get_payee().clone();
+			let multi_id = Self::derive_multi_id(signers);
+
+			let encoded_proof = (multi_id.clone(), lock.hashlock).using_encoded(blake2_256);
+			let proof = Decode::decode(&mut TrailingZeroInput::new(encoded_proof.as_ref()))
+				.map_err(|_| Error::<T>::UnexpectedError)?;
+
+			Self::dispatch_transfer_call(
+				proof,
+				payment_id,
+				payer,
 				payee,
-				false,
+				multi_id.clone(),
+				multi_id,
 			)
-			.map_err(|_| Error::<T>::MultiSigCallFailed)?;
+		}
 
-			let time = <frame_system::Pallet<T>>::block_number();
+		// Refunds a hash locked payment to the payer once its timelock expired, returns the
+		// amount refunded. Nobody is at fault.
+		pub(crate) fn inner_reclaim_htlc(
+			payment_id: PaymentId,
+			signers: AccountSigners<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let lock = HashLocks::<T>::get(payment_id).ok_or(Error::<T>::NotHashLocked)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= lock.expires_at,
+				Error::<T>::TimelockNotExpired
+			);
+			let amount = LockedAmount::<T>::get(payment_id).unwrap_or_default();
 
-			let call_exe_object = CallExecuted::<T>::new(
-				payer.clone(),
-				acc_payee,
-				allowed_multi_id,
-				confirmed_multi_id.clone(),
-				proof,
-				time,
+			let payer = signers.get_payer().clone();
+			let multi_id = Self::derive_multi_id(signers);
+
+			Self::set_status(payment_id, PaymentStatus::Expired)?;
+			Self::refund_multi_id(payment_id, &payer, &multi_id)?;
+
+			Ok(amount)
+		}
+
+		// Opening of a payment without a payee, locked to `commitment` until claimed. The payee
+		// stored in AccountSigners is a keyless account derived from the commitment so nobody
+		// can act as the payee before the claim.
+		pub(crate) fn inner_vane_pay_claimable(
+			payer: T::AccountId,
+			commitment: [u8; 32],
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+		) -> Result<PaymentId, DispatchError> {
+			let placeholder = T::AccountId::decode(&mut TrailingZeroInput::new(&commitment))
+				.map_err(|_| Error::<T>::UnexpectedError)?;
+
+			let accounts = AccountSigners::<T>::new(placeholder, payer, None);
+			let payment_id = Self::open_escrow(accounts, amount, asset)?;
+			ClaimCommitments::<T>::insert(payment_id, commitment);
+
+			Ok(payment_id)
+		}
+
+		// Settles a claimable payment to `claimant` if the secret and the claimant's account
+		// match the commitment. Binding the account keeps the revealed secret from being used
+		// by anyone else.
+		pub(crate) fn inner_claim_payment(
+			payment_id: PaymentId,
+			signers: AccountSigners<T>,
+			claimant: T::AccountId,
+			secret: [u8; 32],
+		) -> DispatchResult {
+			let commitment =
+				ClaimCommitments::<T>::get(payment_id).ok_or(Error::<T>::NotClaimable)?;
+			ensure!(
+				(secret, &claimant).using_encoded(blake2_256) == commitment,
+				Error::<T>::InvalidSecret
 			);
 
-			AccountMultiTxns::<T>::mutate(payer, |vec| vec.push(call_exe_object));
+			let payer = signers.get_payer().clone();
+			let multi_id = Self::derive_multi_id(signers);
 
-			Self::deposit_event(Event::CallExecuted {
-				multi_id: confirmed_multi_id,
-				timestamp: time,
-			});
+			let encoded_proof = (multi_id.clone(), commitment).using_encoded(blake2_256);
+			let proof = Decode::decode(&mut TrailingZeroInput::new(encoded_proof.as_ref()))
+				.map_err(|_| Error::<T>::UnexpectedError)?;
 
-			Ok(())
+			Self::dispatch_transfer_call(
+				proof,
+				payment_id,
+				payer,
+				claimant,
+				multi_id.clone(),
+				multi_id,
+			)
 		}
 
-		// Takes in a multi_id account and register it to Account storage in system pallet
+		// The resolver stored in AccountSigners for the choice made by the payer
+		// `parties` are the payer and the payees, who cannot be the legal team of their payment
+		pub(crate) fn resolver_of_choice(
+			resolver: ResolverChoice,
+			parties: &[&T::AccountId],
+		) -> Result<Option<Resolver<T>>, DispatchError> {
+			Ok(match resolver {
+				ResolverChoice::None => None,
+				ResolverChoice::LegalTeam =>
+					Some(Resolver::LegalTeam(Self::next_legal_team(parties)?)),
+				ResolverChoice::Governance => Some(Resolver::Governance),
+			})
+		}
 
-		pub(crate) fn create_multi_account(multi_id: T::AccountId) -> DispatchResult {
-			let account_info =
-				AccountInfo::<T::Index, T::AccountData> {..Default::default() };
+		// Records the caller's confirmation of a milestone. Once both parties confirmed it, the
+		// milestone is paid to the payee and the last one settles the payment.
+		pub(crate) fn inner_confirm_milestone(
+			payment_id: PaymentId,
+			caller: T::AccountId,
+			signers: AccountSigners<T>,
+			index: u32,
+		) -> DispatchResult {
+			let mut milestones =
+				Milestones::<T>::get(payment_id).ok_or(Error::<T>::NotMilestonePayment)?;
+			let milestone =
+				milestones.get_mut(index as usize).ok_or(Error::<T>::MilestoneNotFound)?;
+			ensure!(!milestone.released, Error::<T>::MilestoneAlreadyReleased);
 
-			// Ensure the multi_id account is not yet registered in the storage
-			// Vijay you must allow to re-use multi_id if its the same payer and payee.
-			if <frame_system::Pallet<T>>::account_exists(&multi_id){
+			let payer = signers.get_payer().clone();
+			let payee = signers.get_payee().clone();
+			let confirmed = if caller == payee {
+				&mut milestone.payee_confirmed
+			} else {
+				&mut milestone.payer_confirmed
+			};
+			ensure!(!*confirmed, Error::<T>::MilestoneAlreadyConfirmed);
+			*confirmed = true;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::MilestoneConfirmed {
+				payment_id,
+				index,
+				account_id: caller,
+				timestamp: time,
+			});
+
+			let release = milestone.payee_confirmed && milestone.payer_confirmed;
+			milestone.released = release;
+			let amount = milestone.amount;
+			let last = milestones.iter().all(|milestone| milestone.released);
+			Milestones::<T>::insert(payment_id, milestones);
+
+			if !release {
 				return Ok(())
-			}else{
-				// Register to frame_system Account Storage item;
-				<frame_system::Account<T>>::set(multi_id, account_info);
-				Ok(())
 			}
 
-		}
+			let multi_id = Self::derive_multi_id(signers);
+			let encoded_proof =
+				(multi_id.clone(), b"vane/milestone", index).using_encoded(blake2_256);
+			let proof = Decode::decode(&mut TrailingZeroInput::new(encoded_proof.as_ref()))
+				.map_err(|_| Error::<T>::UnexpectedError)?;
 
-		// Now , we are only focusing legal team Resolver variant in multi_id generation
+			if last {
+				// What is still locked is exactly this milestone
+				Self::dispatch_transfer_call(
+					proof,
+					payment_id,
+					payer,
+					payee.clone(),
+					multi_id.clone(),
+					multi_id,
+				)?;
+			} else {
+				Self::release_milestone(payment_id, payer, payee.clone(), multi_id, amount, proof)?;
+			}
+
+			Self::deposit_event(Event::MilestoneReleased {
+				payment_id,
+				index,
+				to_account: payee,
+				amount,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Pays a milestone which does not close the payment, the protocol fee is taken from it.
+		// The expiry starts over so the remaining milestones get a full PaymentTimeout.
+		fn release_milestone(
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			payee: T::AccountId,
+			multi_id: T::AccountId,
+			amount: BalanceOf<T>,
+			proof: T::Hash,
+		) -> DispatchResult {
+			let fee = Self::charge_protocol_fee_on(payment_id, &payer, &multi_id, amount)
+				.map_err(|_| Error::<T>::FeeChargeFailed)?;
+			let paid = amount.saturating_sub(fee);
+			Self::release_partial_amount(payment_id, &payer, &multi_id, &payee, paid)
+				.map_err(|_| Error::<T>::MultiSigCallFailed)?;
+			Self::schedule_expiry(payment_id)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			let call_exe_object = CallExecuted::<T>::new(
+				payment_id,
+				payer.clone(),
+				payee.clone(),
+				paid,
+				fee,
+				multi_id.clone(),
+				multi_id,
+				proof,
+				time,
+			);
+			Self::record_history(&payer, &payee, call_exe_object);
+
+			Ok(())
+		}
+
+		// Whether the payee, any payee of a split payment or the payee of any milestone has
+		// confirmed the payment
+		pub(crate) fn any_payee_confirmed(payment_id: PaymentId) -> bool {
+			!ConfirmedSigners::<T>::get(payment_id).is_empty() ||
+				!SplitConfirmations::<T>::get(payment_id).is_empty() ||
+				Milestones::<T>::get(payment_id).map_or(false, |milestones| {
+					milestones.iter().any(|milestone| milestone.payee_confirmed)
+				})
+		}
+
+		// Whether `who` is the payee, or one of the payees of a split payment
+		pub(crate) fn is_payee(
+			payment_id: PaymentId,
+			signers: &AccountSigners<T>,
+			who: &T::AccountId,
+		) -> bool {
+			match SplitPayees::<T>::get(payment_id) {
+				Some(payees) => payees.iter().any(|(payee, _)| payee == who),
+				None => signers.get_payee() == who,
+			}
+		}
+
+		// confirm_pay for split payments. Payees confirm in any order, the payer confirms last and
+		// his confirmation settles the payment.
+		pub(crate) fn confirm_split_pay(
+			who: Confirm,
+			payment_id: PaymentId,
+			caller: T::AccountId,
+			signers: AccountSigners<T>,
+			payees: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPayees>,
+		) -> DispatchResult {
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			match who {
+				Confirm::Payee => {
+					ensure!(
+						payees.iter().any(|(payee, _)| payee == &caller),
+						Error::<T>::NotPaymentParticipant
+					);
+
+					let confirmed = SplitConfirmations::<T>::try_mutate(
+						payment_id,
+						|confirmed| -> Result<usize, DispatchError> {
+							ensure!(
+								!confirmed.contains(&caller),
+								Error::<T>::PayeeAlreadyConfirmed
+							);
+							confirmed
+								.try_push(caller.clone())
+								.map_err(|_| Error::<T>::ExceededSigners)?;
+							Ok(confirmed.len())
+						},
+					)?;
+
+					if confirmed == payees.len() {
+						Self::set_status(payment_id, PaymentStatus::PayeeConfirmed)?;
+					}
+
+					Self::deposit_event(Event::PayeeAddressConfirmed {
+						payment_id,
+						account_id: caller,
+						timestamp: time,
+					});
+
+					Ok(())
+				},
+
+				Confirm::Payer => {
+					ensure!(&caller == signers.get_payer(), Error::<T>::NotPaymentParticipant);
+					ensure!(
+						SplitConfirmations::<T>::get(payment_id).len() == payees.len(),
+						Error::<T>::WaitForPayeeToConfirm
+					);
+
+					Self::deposit_event(Event::PayerAddressConfirmed {
+						payment_id,
+						account_id: caller.clone(),
+						timestamp: time,
+					});
+
+					let payee = signers.get_payee().clone();
+					let confirmed_multi_id = Self::derive_multi_id(
+						AccountSigners::<T>::new(
+							payee.clone(),
+							caller.clone(),
+							signers.get_resolver().clone(),
+						)
+						.with_nonce(signers.get_nonce()),
+					);
+					let allowed_multi_id = Self::derive_multi_id(signers);
+					ensure!(
+						confirmed_multi_id == allowed_multi_id,
+						Error::<T>::FailedToMatchAccounts
+					);
+
+					let encoded_proof = (allowed_multi_id.clone(), confirmed_multi_id.clone())
+						.using_encoded(blake2_256);
+					let proof = Decode::decode(&mut TrailingZeroInput::new(encoded_proof.as_ref()))
+						.map_err(|_| Error::<T>::UnexpectedError)?;
+
+					Self::dispatch_transfer_call(
+						proof,
+						payment_id,
+						caller,
+						payee,
+						allowed_multi_id,
+						confirmed_multi_id,
+					)
+				},
+			}
+		}
+
+		// Round robin selection of an approved legal team account, skipping the parties of the
+		// payment so nobody resolves their own payment
+		pub(crate) fn next_legal_team(
+			parties: &[&T::AccountId],
+		) -> Result<T::AccountId, DispatchError> {
+			let resolvers = ResolverSigner::<T>::get();
+			let count = resolvers.len() as u32;
+			let start = NextResolver::<T>::get();
+
+			for offset in 0..count {
+				let index = start.saturating_add(offset) % count;
+				let candidate = resolvers.get(index as usize).ok_or(Error::<T>::UnexpectedError)?;
+				if !parties.contains(&candidate) {
+					NextResolver::<T>::put(index.saturating_add(1));
+					return Ok(candidate.clone())
+				}
+			}
+
+			Err(Error::<T>::NoResolverAvailable.into())
+		}
+
+		// Issues a new payment id, stores the signers and locks the payer's funds through the
+		// configured escrow backend
+		pub(crate) fn open_escrow(
+			accounts: AccountSigners<T>,
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+		) -> Result<PaymentId, DispatchError> {
+			// Every payment must be able to keep the multi_id alive on its own, also once the
+			// protocol fee was taken out of it
+			let minimum = match asset {
+				PaymentAsset::Native => T::Currency::minimum_balance(),
+				PaymentAsset::Asset(asset_id) =>
+					<T::Assets as fungibles::Inspect<T::AccountId>>::minimum_balance(asset_id),
+			};
+			ensure!(Self::after_protocol_fee(amount) >= minimum, Error::<T>::AmountBelowMinimum);
+
+			let payment_id = NextPaymentId::<T>::get();
+			NextPaymentId::<T>::put(
+				payment_id.checked_add(1).ok_or(Error::<T>::PaymentIdOverflow)?,
+			);
+
+			// Salting with the payment id gives every escrow its own multi_id, even between
+			// the same payer and payee
+			let accounts = accounts.with_nonce(Some(payment_id));
+			let payer = accounts.get_payer().clone();
+			let multi_id = Self::derive_multi_id(accounts.clone());
+
+			// The legal team's fee is the one it advertised when the payment is made
+			if let Some(legal_account) = accounts.get_legal_account() {
+				let fee =
+					Resolvers::<T>::get(legal_account).map_or(Perbill::zero(), |info| info.fee);
+				ResolverFees::<T>::insert(payment_id, fee);
+			}
+
+			AllowedSigners::<T>::insert(payment_id, accounts);
+			LockedAmount::<T>::insert(payment_id, amount);
+			Self::set_status(payment_id, PaymentStatus::Created)?;
+			Self::schedule_expiry(payment_id)?;
+			Self::reserve_penalty_deposit(payment_id, &payer)?;
+
+			if asset != PaymentAsset::Native {
+				PaymentAssets::<T>::insert(payment_id, asset);
+			}
+			Self::lock_funds(payment_id, &payer, &multi_id, amount)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::BalanceTransferredAndLocked {
+				to_multi_id: multi_id,
+				from: payer,
+				timestamp: time,
+			});
+
+			Ok(payment_id)
+		}
+
+		// Dispatching Call helper, only the amount locked for the payment is sent to the payee
+		// so other payments sharing the same multi_id are not affected. The protocol fee is taken
+		// out first and handed to FeeDestination.
+		pub(crate) fn dispatch_transfer_call(
+			proof: T::Hash,
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			payee: T::AccountId,
+			allowed_multi_id: T::AccountId,
+			confirmed_multi_id: T::AccountId,
+		) -> DispatchResult {
+			Self::set_status(payment_id, PaymentStatus::Settled)?;
+
+			let fee = Self::charge_protocol_fee(payment_id, &payer, &allowed_multi_id)
+				.map_err(|_| Error::<T>::FeeChargeFailed)?;
+
+			// A split payment pays every payee his share, others pay the single payee
+			let payouts = match SplitPayees::<T>::get(payment_id) {
+				Some(payees) =>
+					Self::release_split_shares(payment_id, &payer, &allowed_multi_id, &payees, fee),
+				None => Self::release_locked_amount(payment_id, &payer, &allowed_multi_id, &payee)
+					.map(|amount| sp_std::vec![(payee, amount, fee)]),
+			}
+			.map_err(|_| Error::<T>::MultiSigCallFailed)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			// Store the proof and associated data of call execution, once per payee
+			let mut amount: BalanceOf<T> = Zero::zero();
+			for (to_account, paid, paid_fee) in payouts {
+				amount = amount.saturating_add(paid);
+
+				let call_exe_object = CallExecuted::<T>::new(
+					payment_id,
+					payer.clone(),
+					to_account.clone(),
+					paid,
+					paid_fee,
+					allowed_multi_id.clone(),
+					confirmed_multi_id.clone(),
+					proof,
+					time,
+				);
+				Self::record_history(&payer, &to_account, call_exe_object);
+			}
+
+			Self::clear_escrow(payment_id);
+
+			Self::deposit_event(Event::CallExecuted {
+				payment_id,
+				multi_id: confirmed_multi_id,
+				amount,
+				fee,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Pays every payee of a split payment his part of the locked amount, in proportion to his
+		// share. Returns what each payee got and his part of the protocol fee.
+		pub(crate) fn release_split_shares(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			payees: &[(T::AccountId, BalanceOf<T>)],
+			fee: BalanceOf<T>,
+		) -> Result<Vec<(T::AccountId, BalanceOf<T>, BalanceOf<T>)>, DispatchError> {
+			let locked = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			let amounts = Self::split_by_shares(locked, payees);
+			let fees = Self::split_by_shares(fee, payees);
+			let last = payees.len().saturating_sub(1);
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			let mut payouts = Vec::with_capacity(payees.len());
+			for (index, ((payee, _), (amount, paid_fee))) in
+				payees.iter().zip(amounts.into_iter().zip(fees)).enumerate()
+			{
+				// The last payee gets what is left, so rounding never leaves funds locked
+				let paid = if index == last {
+					Self::release_locked_amount(payment_id, payer, multi_id, payee)?
+				} else {
+					Self::release_partial_amount(payment_id, payer, multi_id, payee, amount)?;
+					amount
+				};
+
+				Self::deposit_event(Event::SplitShareReleased {
+					payment_id,
+					to_account: payee.clone(),
+					amount: paid,
+					timestamp: time,
+				});
+
+				payouts.push((payee.clone(), paid, paid_fee));
+			}
+
+			Ok(payouts)
+		}
+
+		// Divides `total` in proportion to the shares, the last payee takes the rounding remainder
+		pub(crate) fn split_by_shares(
+			total: BalanceOf<T>,
+			payees: &[(T::AccountId, BalanceOf<T>)],
+		) -> Vec<BalanceOf<T>> {
+			let shares = payees
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |sum, (_, share)| sum.saturating_add(*share));
+			let mut left = total;
+
+			payees
+				.iter()
+				.enumerate()
+				.map(|(index, (_, share))| {
+					if index + 1 == payees.len() {
+						return left
+					}
+					let part = Perbill::from_rational(*share, shares) * total;
+					left = left.saturating_sub(part);
+					part
+				})
+				.collect()
+		}
+
+		// Stores the settlement in the payer's and in the payee's history
+		pub(crate) fn record_history(
+			payer: &T::AccountId,
+			payee: &T::AccountId,
+			record: CallExecuted<T>,
+		) {
+			Self::push_history::<PayerHistory<T>, PayerHistoryBounds<T>>(payer, record.clone());
+			Self::push_history::<PayeeHistory<T>, PayeeHistoryBounds<T>>(payee, record);
+		}
+
+		// Appends the record and prunes the oldest ones past MaxHistoryPerAccount. At most two are
+		// pruned per record so lowering the limit shrinks long histories gradually.
+		fn push_history<History, Bounds>(who: &T::AccountId, record: CallExecuted<T>)
+		where
+			History: StorageDoubleMapT<T::AccountId, u32, CallExecuted<T>>,
+			Bounds: StorageMapT<T::AccountId, HistoryBounds, Query = HistoryBounds>,
+		{
+			Bounds::mutate(who, |bounds| {
+				History::insert(who, bounds.next, record);
+				bounds.next = bounds.next.saturating_add(1);
+
+				for _ in 0..2 {
+					if bounds.next.saturating_sub(bounds.first) <= T::MaxHistoryPerAccount::get() {
+						break
+					}
+					History::remove(who, bounds.first);
+					bounds.first = bounds.first.saturating_add(1);
+				}
+			});
+		}
+
+		// Up to `limit` settled payments of `who` as payer, oldest first, starting at sequence
+		// number `start`. Also returns the sequence number to start the next page from.
+		pub fn payer_history_page(
+			who: &T::AccountId,
+			start: u32,
+			limit: u32,
+		) -> (Vec<CallExecuted<T>>, Option<u32>) {
+			Self::history_page::<PayerHistory<T>, PayerHistoryBounds<T>>(who, start, limit)
+		}
+
+		// Same as payer_history_page for the payments `who` received
+		pub fn payee_history_page(
+			who: &T::AccountId,
+			start: u32,
+			limit: u32,
+		) -> (Vec<CallExecuted<T>>, Option<u32>) {
+			Self::history_page::<PayeeHistory<T>, PayeeHistoryBounds<T>>(who, start, limit)
+		}
+
+		fn history_page<History, Bounds>(
+			who: &T::AccountId,
+			start: u32,
+			limit: u32,
+		) -> (Vec<CallExecuted<T>>, Option<u32>)
+		where
+			History: StorageDoubleMapT<
+				T::AccountId,
+				u32,
+				CallExecuted<T>,
+				Query = Option<CallExecuted<T>>,
+			>,
+			Bounds: StorageMapT<T::AccountId, HistoryBounds, Query = HistoryBounds>,
+		{
+			let bounds = Bounds::get(who);
+			let from = start.max(bounds.first);
+			let limit = limit.min(T::MaxHistoryPerAccount::get());
+			let to = from.saturating_add(limit).min(bounds.next);
+
+			let records = (from..to).filter_map(|seq| History::get(who, seq)).collect();
+			let next = if to < bounds.next { Some(to) } else { None };
+
+			(records, next)
+		}
+
+		// Takes ProtocolFee of the payment's locked amount out of the escrow, the payee is paid
+		// what is left
+		pub(crate) fn charge_protocol_fee(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let locked = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			Self::charge_protocol_fee_on(payment_id, payer, multi_id, locked)
+		}
+
+		// What is left of `amount` once ProtocolFee is taken out of it
+		pub(crate) fn after_protocol_fee(amount: BalanceOf<T>) -> BalanceOf<T> {
+			amount.saturating_sub(T::ProtocolFee::get() * amount)
+		}
+
+		// Takes ProtocolFee of `amount`, a part of the payment's locked amount, out of the escrow
+		pub(crate) fn charge_protocol_fee_on(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let locked = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			let fee = T::ProtocolFee::get() * amount;
+
+			if fee.is_zero() {
+				return Ok(fee)
+			}
+
+			match PaymentAssets::<T>::get(payment_id) {
+				PaymentAsset::Native => {
+					let imbalance = T::Escrow::withdraw(payment_id, payer, multi_id, fee)?;
+					T::FeeDestination::on_unbalanced(imbalance);
+				},
+				PaymentAsset::Asset(asset_id) => {
+					let destination = T::AssetFeeDestination::get();
+					// A fee too small to be credited to the fee account is not charged
+					if <T::Assets as fungibles::Inspect<T::AccountId>>::can_deposit(
+						asset_id,
+						&destination,
+						fee,
+						false,
+					) != DepositConsequence::Success
+					{
+						return Ok(Zero::zero())
+					}
+					<T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+						asset_id,
+						multi_id,
+						&destination,
+						fee,
+						true,
+					)?;
+				},
+			}
+			LockedAmount::<T>::insert(payment_id, locked.saturating_sub(fee));
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::ProtocolFeeCharged { payment_id, fee, timestamp: time });
+
+			Ok(fee)
+		}
+
+		// Refunding Call helper, sends the payment's locked amount back to the payer and clears
+		// the signers storage for that payment
+		pub(crate) fn dispatch_refund_call(
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			multi_id: T::AccountId,
+			reason: RevertReasons,
+		) -> DispatchResult {
+			Self::set_status(payment_id, PaymentStatus::Refunded)?;
+			Self::refund_multi_id(payment_id, &payer, &multi_id)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::FundReverted {
+				payment_id,
+				multi_id,
+				to_account: payer,
+				reason,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Moves the payment's locked amount from multi_id back to the payer and clears the escrow
+		pub(crate) fn refund_multi_id(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+		) -> DispatchResult {
+			Self::release_locked_amount(payment_id, payer, multi_id, payer)
+				.map_err(|_| Error::<T>::RevertCallFailed)?;
+
+			Self::clear_escrow(payment_id);
+
+			Ok(())
+		}
+
+		// Sends exactly the payment's locked amount to `to` and closes the payment's escrow
+		pub(crate) fn release_locked_amount(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			to: &T::AccountId,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let amount = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+
+			Self::release_funds(payment_id, payer, multi_id, to, amount, true)?;
+
+			Ok(amount)
+		}
+
+		// Sends part of a payment's locked amount, the rest of the payment stays held
+		pub(crate) fn release_partial_amount(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			to: &T::AccountId,
+			part: BalanceOf<T>,
+		) -> DispatchResult {
+			let locked = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			ensure!(locked >= part, Error::<T>::EscrowAccountingFailed);
+
+			Self::release_funds(payment_id, payer, multi_id, to, part, false)?;
+			LockedAmount::<T>::insert(payment_id, locked.saturating_sub(part));
+
+			Ok(())
+		}
+
+		// Holds the payer's funds for the payment, native ones through the configured escrow
+		// backend and other assets in the multi_id
+		pub(crate) fn lock_funds(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			match PaymentAssets::<T>::get(payment_id) {
+				PaymentAsset::Native => T::Escrow::lock(payment_id, payer, multi_id, amount),
+				PaymentAsset::Asset(asset_id) => {
+					// Every open asset payment keeps the multi_id alive, even for assets which
+					// are not sufficient on their own
+					<frame_system::Pallet<T>>::inc_providers(multi_id);
+					<T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+						asset_id, payer, multi_id, amount, true,
+					)
+					.map(drop)
+				},
+			}
+		}
+
+		// Sends `amount` of the payment's held funds to `to`, through the matching asset. `close`
+		// is true when nothing of the payment stays held afterwards.
+		pub(crate) fn release_funds(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+			multi_id: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+			close: bool,
+		) -> DispatchResult {
+			match PaymentAssets::<T>::get(payment_id) {
+				PaymentAsset::Native =>
+					T::Escrow::release(payment_id, payer, multi_id, to, amount, close),
+				PaymentAsset::Asset(asset_id) => {
+					// Partial releases must keep the multi_id's asset account alive
+					<T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+						asset_id, multi_id, to, amount, !close,
+					)?;
+					if close {
+						let _ = <frame_system::Pallet<T>>::dec_providers(multi_id);
+					}
+					Ok(())
+				},
+			}
+		}
+
+		// Removes everything stored for a payment once it is settled or refunded
+		pub(crate) fn clear_escrow(payment_id: PaymentId) {
+			AllowedSigners::<T>::remove(payment_id);
+			ConfirmedSigners::<T>::remove(payment_id);
+			LockedAmount::<T>::remove(payment_id);
+			RevertRequests::<T>::remove(payment_id);
+			PaymentExpiry::<T>::remove(payment_id);
+			SplitPayees::<T>::remove(payment_id);
+			SplitConfirmations::<T>::remove(payment_id);
+			Milestones::<T>::remove(payment_id);
+			HashLocks::<T>::remove(payment_id);
+			ClaimCommitments::<T>::remove(payment_id);
+			RefundProposals::<T>::remove(payment_id);
+			PaymentAssets::<T>::remove(payment_id);
+			ResolverFees::<T>::remove(payment_id);
+			if let Some((resolver, _)) = DisputeResolvers::<T>::take(payment_id) {
+				Self::release_assignment(&resolver);
+			}
+			if let Some(ruling) = Rulings::<T>::take(payment_id) {
+				Self::release_assignment(&ruling.resolver);
+			}
+
+			if let Some((payer, deposit)) = PaymentDeposits::<T>::take(payment_id) {
+				T::Currency::unreserve(&payer, deposit);
+			}
+			if let Some(dispute) = Disputes::<T>::take(payment_id) {
+				T::Currency::unreserve(&dispute.opened_by, dispute.deposit);
+			}
+		}
+
+		// Payer, payee and legal team cannot settle or refund a payment waiting for a dispute
+		// decision, only resolve_dispute can
+		pub(crate) fn ensure_not_disputed(payment_id: PaymentId) -> DispatchResult {
+			ensure!(
+				PaymentStatuses::<T>::get(payment_id) != Some(PaymentStatus::Disputed),
+				Error::<T>::PaymentDisputed
+			);
+
+			Ok(())
+		}
+
+		// The payees of a split payment only agree one by one, a call the lead payee alone agrees
+		// to cannot move the shares of the others
+		pub(crate) fn ensure_not_split(payment_id: PaymentId) -> DispatchResult {
+			ensure!(
+				!SplitPayees::<T>::contains_key(payment_id),
+				Error::<T>::SplitPaymentNotSupported
+			);
+
+			Ok(())
+		}
+
+		// The payer alone cannot take back a hash locked payment before its timelock, nor a
+		// claimable payment before its escrow expires
+		pub(crate) fn ensure_not_locked_to_payee(payment_id: PaymentId) -> DispatchResult {
+			ensure!(!HashLocks::<T>::contains_key(payment_id), Error::<T>::TimelockNotExpired);
+			ensure!(
+				!ClaimCommitments::<T>::contains_key(payment_id),
+				Error::<T>::ClaimableUntilExpiry
+			);
+
+			Ok(())
+		}
+
+		pub(crate) fn can_set_status(payment_id: PaymentId, next: &PaymentStatus) -> bool {
+			match PaymentStatuses::<T>::get(payment_id) {
+				Some(status) => status.can_transition_to(next),
+				None => next == &PaymentStatus::Created,
+			}
+		}
+
+		// Moves the payment to `next` if the transition is legal and reports it
+		pub(crate) fn set_status(payment_id: PaymentId, next: PaymentStatus) -> DispatchResult {
+			let previous = PaymentStatuses::<T>::get(payment_id);
+			ensure!(Self::can_set_status(payment_id, &next), Error::<T>::InvalidStatusTransition);
+
+			PaymentStatuses::<T>::insert(payment_id, next);
+			if next.is_final() {
+				Self::schedule_status_pruning(payment_id);
+			}
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::PaymentStatusChanged {
+				payment_id,
+				previous,
+				status: next,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Reserves the penalty deposit of a payer with too many faults for a new payment
+		pub(crate) fn reserve_penalty_deposit(
+			payment_id: PaymentId,
+			payer: &T::AccountId,
+		) -> DispatchResult {
+			let deposit = Self::required_deposit(payer);
+			if deposit.is_zero() {
+				return Ok(())
+			}
+
+			T::Currency::reserve(payer, deposit)?;
+			PaymentDeposits::<T>::insert(payment_id, (payer.clone(), deposit));
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::PenaltyDepositReserved {
+				payment_id,
+				account_id: payer.clone(),
+				deposit,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Faults of an account within FaultWindow, as payer and as payee
+		pub(crate) fn fault_count(who: &T::AccountId) -> u32 {
+			RevertedTxnPayer::<T>::get(who).saturating_add(RevertedTxnPayee::<T>::get(who))
+		}
+
+		// PenaltyDeposit for every fault counted from DepositThreshold
+		pub(crate) fn required_deposit(who: &T::AccountId) -> BalanceOf<T> {
+			let faults = Self::fault_count(who);
+			let threshold = T::DepositThreshold::get();
+			if faults < threshold {
+				return Zero::zero()
+			}
+
+			let counted = faults.saturating_sub(threshold).saturating_add(1);
+			T::PenaltyDeposit::get().saturating_mul(counted.into())
+		}
+
+		// Fails while the account is barred from vane_pay and confirm_pay, an expired bar is
+		// removed on the way
+		pub(crate) fn ensure_not_barred(who: &T::AccountId) -> DispatchResult {
+			if let Some(until) = BarredUntil::<T>::get(who) {
+				let now = <frame_system::Pallet<T>>::block_number();
+				ensure!(now >= until, Error::<T>::AccountBarred);
+				BarredUntil::<T>::remove(who);
+			}
+
+			Ok(())
+		}
+
+		// Records a fault against the account, schedules it to expire after FaultWindow and
+		// applies the punishments whose thresholds are reached. A full decay slot spills into the
+		// next block's; past that the fault is still counted but never expires, bookkeeping must
+		// not abort the settlement or refund recording it.
+		pub(crate) fn record_fault(who: &T::AccountId, role: FaultRole) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let decay_at = now.saturating_add(T::FaultWindow::get());
+
+			for at in [decay_at, decay_at.saturating_add(One::one())] {
+				let pushed = FaultDecay::<T>::try_mutate(at, |faults| {
+					faults.try_push((who.clone(), role.clone()))
+				});
+				if pushed.is_ok() {
+					break
+				}
+			}
+
+			match role {
+				FaultRole::Payer =>
+					RevertedTxnPayer::<T>::mutate(who, |count| *count = count.saturating_add(1)),
+				FaultRole::Payee =>
+					RevertedTxnPayee::<T>::mutate(who, |count| *count = count.saturating_add(1)),
+			};
+
+			let faults = Self::fault_count(who);
+
+			Self::deposit_event(Event::FaultRecorded {
+				account_id: who.clone(),
+				role,
+				faults,
+				timestamp: now,
+			});
+
+			if faults >= T::DepositThreshold::get() {
+				Self::deposit_event(Event::DepositRaised {
+					account_id: who.clone(),
+					deposit: Self::required_deposit(who),
+					faults,
+					timestamp: now,
+				});
+			}
+
+			if faults >= T::BarThreshold::get() {
+				let until = now.saturating_add(T::BarDuration::get());
+				BarredUntil::<T>::insert(who, until);
+
+				Self::deposit_event(Event::AccountBarred {
+					account_id: who.clone(),
+					until,
+					faults,
+					timestamp: now,
+				});
+			}
+
+			if faults > T::SlashThreshold::get() {
+				// Only the free balance above the existential deposit is slashed, reserved funds
+				// back escrows, deposits and bonds of their own
+				let slashable = T::Currency::free_balance(who)
+					.saturating_sub(T::Currency::minimum_balance())
+					.min(T::FaultPenalty::get());
+				let (imbalance, _) = T::Currency::slash(who, slashable);
+				let amount = imbalance.peek();
+				T::FeeDestination::on_unbalanced(imbalance);
+
+				Self::deposit_event(Event::FaultPenaltySlashed {
+					account_id: who.clone(),
+					amount,
+					faults,
+					timestamp: now,
+				});
+			}
+		}
+
+		// Called from on_initialize, takes the faults recorded FaultWindow blocks ago off the
+		// counters. Bars and deposits already in place are not lifted. Returns the number of
+		// faults taken off.
+		pub(crate) fn decay_faults(now: T::BlockNumber) -> u32 {
+			let faults = FaultDecay::<T>::take(now);
+			let count = faults.len() as u32;
+
+			for (who, role) in faults {
+				let decrement = |count: &mut u32| *count = count.saturating_sub(1);
+				match role {
+					FaultRole::Payer => RevertedTxnPayer::<T>::mutate(&who, decrement),
+					FaultRole::Payee => RevertedTxnPayee::<T>::mutate(&who, decrement),
+				};
+
+				Self::deposit_event(Event::FaultExpired {
+					faults: Self::fault_count(&who),
+					account_id: who,
+					role,
+					timestamp: now,
+				});
+			}
+
+			count
+		}
+
+		// Registers the status of a closed payment to be removed after StatusRetention blocks
+		fn schedule_status_pruning(payment_id: PaymentId) {
+			// At least the next block, this one may be pruned already
+			let at = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::StatusRetention::get().max(One::one()));
+			ClosedPayments::<T>::insert(at, payment_id, ());
+		}
+
+		// Called from on_initialize, removes the status and the rejection reason of payments
+		// closed StatusRetention blocks ago. Walks the blocks from NextPrunedBlock up to `now`,
+		// every pruned payment and every block checked counting against MaxClosedPerBlock, and
+		// leaves the rest to the next blocks. Returns the number of payments and blocks handled.
+		pub(crate) fn prune_closed_payments(now: T::BlockNumber) -> u32 {
+			let mut block = NextPrunedBlock::<T>::get();
+			// Nothing was closed before the hook first ran
+			if block.is_zero() {
+				block = now;
+			}
+
+			let limit = T::MaxClosedPerBlock::get();
+			let mut handled: u32 = 0;
+			while block <= now && handled < limit {
+				let left = limit.saturating_sub(handled);
+				let due: Vec<PaymentId> = ClosedPayments::<T>::drain_prefix(block)
+					.take(left as usize)
+					.map(|(payment_id, _)| payment_id)
+					.collect();
+				for payment_id in due.iter() {
+					PaymentStatuses::<T>::remove(payment_id);
+					RejectionReasons::<T>::remove(payment_id);
+				}
+				handled = handled.saturating_add(due.len() as u32);
+
+				// The block may hold more payments than were taken
+				if due.len() as u32 == left {
+					break
+				}
+				handled = handled.saturating_add(1);
+				block = block.saturating_add(One::one());
+			}
+			NextPrunedBlock::<T>::put(block);
+
+			handled
+		}
+
+		// Registers the payment's escrow to be expired after PaymentTimeout blocks
+		pub(crate) fn schedule_expiry(payment_id: PaymentId) -> DispatchResult {
+			let expiry = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::PaymentTimeout::get());
+
+			ExpiringPayments::<T>::try_mutate(expiry, |payments| payments.try_push(payment_id))
+				.map_err(|_| Error::<T>::TooManyExpiringPayments)?;
+			PaymentExpiry::<T>::insert(payment_id, expiry);
+
+			Ok(())
+		}
+
+		// Called from on_initialize, refunds escrows expiring at `now` and records the fault to
+		// the party which did not confirm the payment in time. Returns the number of payments
+		// which were due.
+		pub(crate) fn refund_expired_payments(now: T::BlockNumber) -> u32 {
+			let payments = ExpiringPayments::<T>::take(now);
+			let count = payments.len() as u32;
+
+			for payment_id in payments {
+				// The payment has been settled or reverted in the meantime. Disputed payments are
+				// left to governance.
+				if PaymentExpiry::<T>::get(payment_id) != Some(now) ||
+					!Self::can_set_status(payment_id, &PaymentStatus::Expired)
+				{
+					continue
+				}
+				let allowed_signers = match AllowedSigners::<T>::get(payment_id) {
+					Some(signers) => signers,
+					None => continue,
+				};
+
+				let payer = allowed_signers.get_payer().clone();
+				let payee = allowed_signers.get_payee().clone();
+				let multi_id = Self::derive_multi_id(allowed_signers);
+
+				// Every payee of a split payment who did not confirm is at fault, the payee of a
+				// milestone payment is not once the pending milestone has the payee's confirmation.
+				// Not claiming a hash locked or claimable payment is no fault either. Once the
+				// payees confirmed, the payer who never did is the one at fault.
+				let payer_late = PaymentStatuses::<T>::get(payment_id) ==
+					Some(PaymentStatus::PayeeConfirmed);
+				let payee_waiting = payer_late ||
+					ConfirmedSigners::<T>::get(payment_id).contains(&payee) ||
+					HashLocks::<T>::contains_key(payment_id) ||
+					ClaimCommitments::<T>::contains_key(payment_id) ||
+					Milestones::<T>::get(payment_id).map_or(false, |milestones| {
+						milestones
+							.iter()
+							.any(|milestone| !milestone.released && milestone.payee_confirmed)
+					});
+				let late_payees: Vec<T::AccountId> = match SplitPayees::<T>::get(payment_id) {
+					Some(payees) => {
+						let confirmed = SplitConfirmations::<T>::get(payment_id);
+						payees
+							.into_iter()
+							.map(|(payee, _)| payee)
+							.filter(|payee| !confirmed.contains(payee))
+							.collect()
+					},
+					None if payee_waiting => Vec::new(),
+					None => sp_std::vec![payee.clone()],
+				};
+
+				// A refund failing halfway is rolled back as a whole
+				let refunded = with_transaction(|| {
+					let result = Self::refund_multi_id(payment_id, &payer, &multi_id)
+						.and_then(|_| Self::set_status(payment_id, PaymentStatus::Expired));
+					match result {
+						Ok(()) => TransactionOutcome::Commit(result),
+						Err(_) => TransactionOutcome::Rollback(result),
+					}
+				});
+
+				match refunded {
+					Ok(()) => {
+						for late_payee in late_payees.iter() {
+							Self::record_fault(late_payee, FaultRole::Payee);
+						}
+						if payer_late {
+							Self::record_fault(&payer, FaultRole::Payer);
+						}
+
+						Self::deposit_event(Event::EscrowExpired {
+							payment_id,
+							multi_id,
+							payer,
+							payee,
+							timestamp: now,
+						});
+					},
+					Err(error) => {
+						// The escrow is still whole, it is retried in the next block
+						let retry_at = now.saturating_add(One::one());
+						let rescheduled = ExpiringPayments::<T>::try_mutate(retry_at, |payments| {
+							payments.try_push(payment_id)
+						})
+						.is_ok();
+						if rescheduled {
+							PaymentExpiry::<T>::insert(payment_id, retry_at);
+						}
+
+						Self::deposit_event(Event::ExpiryFailed {
+							payment_id,
+							error,
+							retry_at: rescheduled.then(|| retry_at),
+							timestamp: now,
+						});
+					},
+				}
+			}
+
+			count
+		}
+
+		// Pays the payer back their part of an agreed partial refund and settles the rest to the
+		// payee, returns the amount refunded
+		pub(crate) fn inner_accept_refund(
+			payment_id: PaymentId,
+			signers: AccountSigners<T>,
+			payee_share: Perbill,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let locked = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			let refunded = locked.saturating_sub(payee_share * locked);
+
+			let payer = signers.get_payer().clone();
+			let payee = signers.get_payee().clone();
+			let multi_id = Self::derive_multi_id(signers);
+
+			Self::release_partial_amount(payment_id, &payer, &multi_id, &payer, refunded)
+				.map_err(|_| Error::<T>::MultiSigCallFailed)?;
+
+			let encoded_proof = (multi_id.clone(), b"vane/partial-refund", payee_share)
+				.using_encoded(blake2_256);
+			let proof = Decode::decode(&mut TrailingZeroInput::new(encoded_proof.as_ref()))
+				.map_err(|_| Error::<T>::UnexpectedError)?;
+
+			Self::dispatch_transfer_call(
+				proof,
+				payment_id,
+				payer,
+				payee,
+				multi_id.clone(),
+				multi_id,
+			)?;
+
+			Ok(refunded)
+		}
+
+		// Executes a dispute verdict on the funds locked in the multi_id
+		pub(crate) fn inner_resolve_dispute(
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			payee: T::AccountId,
+			multi_id: T::AccountId,
+			verdict: DisputeVerdict,
+		) -> DispatchResult {
+			match verdict {
+				DisputeVerdict::RefundPayer => {
+					// Every payee of a split payment who did not confirm is at fault
+					match SplitPayees::<T>::get(payment_id) {
+						Some(payees) => {
+							let confirmed = SplitConfirmations::<T>::get(payment_id);
+							for (late_payee, _) in
+								payees.iter().filter(|(payee, _)| !confirmed.contains(payee))
+							{
+								Self::record_fault(late_payee, FaultRole::Payee);
+							}
+						},
+						None => Self::record_fault(&payee, FaultRole::Payee),
+					}
+					return Self::dispatch_refund_call(
+						payment_id,
+						payer,
+						multi_id,
+						RevertReasons::PayeeMisbehaviour,
+					)
+				},
+				DisputeVerdict::ReleaseToPayee => {
+					Self::record_fault(&payer, FaultRole::Payer);
+				},
+				DisputeVerdict::Split(payee_share) => {
+					// Send the payer's part first, the rest goes to the payee below
+					let locked = LockedAmount::<T>::get(payment_id)
+						.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+					let payer_part = locked.saturating_sub(payee_share * locked);
+
+					Self::release_partial_amount(payment_id, &payer, &multi_id, &payer, payer_part)
+						.map_err(|_| Error::<T>::MultiSigCallFailed)?;
+				},
+			};
+
+			let encoded_proof = (multi_id.clone(), b"vane/governance").using_encoded(blake2_256);
+			let proof = Decode::decode(&mut TrailingZeroInput::new(encoded_proof.as_ref()))
+				.map_err(|_| Error::<T>::UnexpectedError)?;
+
+			Self::dispatch_transfer_call(
+				proof,
+				payment_id,
+				payer,
+				payee,
+				multi_id.clone(),
+				multi_id,
+			)
+		}
+
+		// Applies the final verdict of a dispute and settles its deposit. `resolver` is the
+		// legal team whose ruling is applied, it is paid its fee from the escrow first.
+		pub(crate) fn settle_dispute(
+			payment_id: PaymentId,
+			verdict: DisputeVerdict,
+			resolver: Option<T::AccountId>,
+		) -> DispatchResult {
+			// Taken before the escrow is cleared so the deposit is settled once, below
+			let dispute = Disputes::<T>::take(payment_id).ok_or(Error::<T>::NoDisputeRaised)?;
+			let allowed_signers = AllowedSigners::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+
+			let payer = allowed_signers.get_payer().clone();
+			let payee = allowed_signers.get_payee().clone();
+			let multi_id = Self::derive_multi_id(allowed_signers);
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			if let Some(resolver) = resolver {
+				let locked = LockedAmount::<T>::get(payment_id)
+					.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+				let fee = ResolverFees::<T>::get(payment_id) * locked;
+
+				if !fee.is_zero() {
+					Self::release_partial_amount(payment_id, &payer, &multi_id, &resolver, fee)
+						.map_err(|_| Error::<T>::MultiSigCallFailed)?;
+
+					Self::deposit_event(Event::ResolverFeePaid {
+						payment_id,
+						resolver,
+						amount: fee,
+						timestamp: time,
+					});
+				}
+			}
+
+			Self::inner_resolve_dispute(
+				payment_id,
+				payer.clone(),
+				payee.clone(),
+				multi_id.clone(),
+				verdict.clone(),
+			)?;
+			Self::settle_dispute_deposit(payment_id, dispute, &payer, &payee, &verdict)?;
+
+			Self::deposit_event(Event::DisputeResolved {
+				payment_id,
+				multi_id,
+				verdict,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Records the verdict of the legal team assigned to an open dispute. It is applied by
+		// execute_ruling once AppealPeriod passed, or decided by DisputeOrigin if appealed.
+		pub(crate) fn record_ruling(
+			payment_id: PaymentId,
+			resolver: T::AccountId,
+			verdict: DisputeVerdict,
+		) -> DispatchResult {
+			ensure!(Disputes::<T>::contains_key(payment_id), Error::<T>::NoDisputeRaised);
+			let (assigned, _) =
+				DisputeResolvers::<T>::get(payment_id).ok_or(Error::<T>::NotAllowedToResolve)?;
+			ensure!(resolver == assigned, Error::<T>::NotAllowedToResolve);
+
+			let time = <frame_system::Pallet<T>>::block_number();
+			let appeal_ends = time.saturating_add(T::AppealPeriod::get());
+
+			DisputeResolvers::<T>::remove(payment_id);
+			Rulings::<T>::insert(
+				payment_id,
+				Ruling {
+					resolver: resolver.clone(),
+					verdict: verdict.clone(),
+					appeal_ends,
+					appealed: false,
+				},
+			);
+
+			Self::deposit_event(Event::RulingMade {
+				payment_id,
+				resolver,
+				verdict,
+				appeal_ends,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Slashes ResolverSlash of the bond of a legal team whose ruling governance overturned.
+		// Below ResolverBond it is no longer chosen until it tops the bond up.
+		pub(crate) fn slash_resolver(payment_id: PaymentId, resolver: &T::AccountId) {
+			let slashed = Resolvers::<T>::mutate(resolver, |maybe_info| {
+				let info = maybe_info.as_mut()?;
+				let (imbalance, _) =
+					T::Currency::slash_reserved(resolver, T::ResolverSlash::get() * info.bond);
+				let amount = imbalance.peek();
+				T::FeeDestination::on_unbalanced(imbalance);
+				info.bond = info.bond.saturating_sub(amount);
+				Some((amount, info.bond < T::ResolverBond::get()))
+			});
+			// Removed from the registry in the meantime, its bond was released
+			let (amount, below_bond) = match slashed {
+				Some(slashed) => slashed,
+				None => return,
+			};
+
+			if below_bond {
+				ResolverSigner::<T>::mutate(|resolvers| resolvers.retain(|r| r != resolver));
+			}
+
+			Self::deposit_event(Event::ResolverSlashed {
+				payment_id,
+				account_id: resolver.clone(),
+				amount,
+				timestamp: <frame_system::Pallet<T>>::block_number(),
+			});
+		}
+
+		// Gives the legal team RulingPeriod blocks to rule on the dispute, returns the deadline
+		pub(crate) fn assign_dispute_resolver(
+			payment_id: PaymentId,
+			resolver: T::AccountId,
+		) -> Result<T::BlockNumber, DispatchError> {
+			let deadline = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(T::RulingPeriod::get());
+
+			RulingDeadlines::<T>::try_mutate(deadline, |payments| payments.try_push(payment_id))
+				.map_err(|_| Error::<T>::TooManyRulingDeadlines)?;
+			ResolverAssignments::<T>::mutate(&resolver, |count| *count = count.saturating_add(1));
+			DisputeResolvers::<T>::insert(payment_id, (resolver, deadline));
+
+			Ok(deadline)
+		}
+
+		// A dispute or ruling of the legal team was settled or handed to another legal team
+		pub(crate) fn release_assignment(resolver: &T::AccountId) {
+			ResolverAssignments::<T>::mutate_exists(resolver, |count| {
+				*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+			});
+		}
+
+		// The next bonded legal team in turns which is neither `late` nor a party of the payment
+		pub(crate) fn replacement_resolver(
+			payment_id: PaymentId,
+			signers: &AccountSigners<T>,
+			late: &T::AccountId,
+		) -> Option<T::AccountId> {
+			let resolvers = ResolverSigner::<T>::get();
+			let count = resolvers.len() as u32;
+			let start = NextResolver::<T>::get();
+
+			for offset in 0..count {
+				let index = start.saturating_add(offset) % count;
+				let candidate = resolvers.get(index as usize)?;
+				if candidate != late &&
+					candidate != signers.get_payer() &&
+					!Self::is_payee(payment_id, signers, candidate)
+				{
+					NextResolver::<T>::put(index.saturating_add(1));
+					return Some(candidate.clone())
+				}
+			}
+
+			None
+		}
+
+		// Called from on_initialize, hands the disputes whose legal team did not rule by `now`
+		// to the next legal team. Without one left, governance decides the dispute. Returns the
+		// number of disputes whose deadline was due.
+		pub(crate) fn replace_late_resolvers(now: T::BlockNumber) -> u32 {
+			let payments = RulingDeadlines::<T>::take(now);
+			let count = payments.len() as u32;
+
+			for payment_id in payments {
+				// The legal team ruled or the dispute was decided in the meantime
+				let late = match DisputeResolvers::<T>::get(payment_id) {
+					Some((resolver, deadline)) if deadline == now => resolver,
+					_ => continue,
+				};
+				let allowed_signers = match AllowedSigners::<T>::get(payment_id) {
+					Some(signers) => signers,
+					None => continue,
+				};
+				Self::release_assignment(&late);
+
+				let assigned = Self::replacement_resolver(payment_id, &allowed_signers, &late)
+					.and_then(|resolver| {
+						Self::assign_dispute_resolver(payment_id, resolver.clone())
+							.ok()
+							.map(|deadline| (resolver, deadline))
+					});
+				if assigned.is_none() {
+					DisputeResolvers::<T>::remove(payment_id);
+				}
+
+				Self::deposit_event(Event::ResolverReplaced {
+					payment_id,
+					replaced: late,
+					resolver: assigned.clone().map(|(resolver, _)| resolver),
+					deadline: assigned.map(|(_, deadline)| deadline),
+					timestamp: now,
+				});
+			}
+
+			count
+		}
+
+		// The dispute deposit goes to the other party when the verdict is fully against the one
+		// who opened the dispute, and back to the opener otherwise
+		pub(crate) fn settle_dispute_deposit(
+			payment_id: PaymentId,
+			dispute: Dispute<T>,
+			payer: &T::AccountId,
+			payee: &T::AccountId,
+			verdict: &DisputeVerdict,
+		) -> DispatchResult {
+			let opened_by_payer = &dispute.opened_by == payer;
+			let lost = match verdict {
+				DisputeVerdict::ReleaseToPayee => opened_by_payer,
+				DisputeVerdict::RefundPayer => !opened_by_payer,
+				DisputeVerdict::Split(_) => false,
+			};
+
+			if !lost || dispute.deposit.is_zero() {
+				T::Currency::unreserve(&dispute.opened_by, dispute.deposit);
+				return Ok(())
+			}
+
+			let to = if opened_by_payer { payee } else { payer };
+			T::Currency::repatriate_reserved(
+				&dispute.opened_by,
+				to,
+				dispute.deposit,
+				BalanceStatus::Free,
+			)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::DisputeDepositForfeited {
+				payment_id,
+				from_account: dispute.opened_by,
+				to_account: to.clone(),
+				amount: dispute.deposit,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Takes in a multi_id account and register it to Account storage in system pallet
+
+		pub(crate) fn create_multi_account(multi_id: T::AccountId) -> DispatchResult {
+			let account_info =
+				AccountInfo::<T::Index, T::AccountData> {..Default::default() };
+
+			// The nonce already gives every escrow an account of its own. An existing one was
+			// funded by someone else ahead of the payment, e.g. to block it, and is reused: the
+			// stray funds are swept back to the payer when the escrow is closed.
+			if <frame_system::Pallet<T>>::account_exists(&multi_id){
+				return Ok(())
+			}else{
+				// Register to frame_system Account Storage item;
+				<frame_system::Account<T>>::set(multi_id, account_info);
+				Ok(())
+			}
+
+		}
+
+		// Now , we are only focusing legal team Resolver variant in multi_id generation
 		// We can do better on this function definition
 		pub(crate) fn derive_multi_id(account_object: AccountSigners<T>) -> T::AccountId {
 			let (acc1, acc2, opt_acc3) = match account_object.get_resolver() {
@@ -260,7 +2292,14 @@ pub mod utils {
 				None => (account_object.get_payee(), account_object.get_payer(), None),
 			};
 
-			let multi_account = if let Some(acc3) = opt_acc3 {
+			// The genesis hash keeps a multi_id from being replayed on another chain
+			let multi_account = if let Some(nonce) = account_object.get_nonce() {
+				let genesis_hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+				let entropy = (b"vane/salt", genesis_hash, nonce, acc1, acc2, opt_acc3)
+					.using_encoded(blake2_256);
+				Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+					.expect("infinite length input; no invalid inputs for type; qed")
+			} else if let Some(acc3) = opt_acc3 {
 				let entropy = (b"vane/salt", acc1, acc2, acc3).using_encoded(blake2_256);
 				Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
 					.expect("infinite length input; no invalid inputs for type; qed")
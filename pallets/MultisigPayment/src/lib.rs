@@ -20,7 +20,11 @@
 
 //------------------------------------------------------------------------------------------//
 // We must keep track of seller and buyer bad behaviours in storage item so that we can introduce
-// further punishments when bad repeated behaviour occurs
+// further punishments when bad repeated behaviour occurs.
+// Faults recorded in RevertedTxnPayer and RevertedTxnPayee only count for FaultWindow blocks.
+// Past the configured thresholds the account has to reserve a deposit for new payments, is
+// barred from vane_pay and confirm_pay for a while and finally has a penalty slashed
+// from its free balance.
 
 pub use pallet::*;
 
@@ -28,80 +32,399 @@ pub use pallet::*;
 mod mock;
 
 mod helper;
+pub use helper::{CallExecuted, EscrowBackend, MultiIdEscrow, ReserveEscrow};
+pub mod migrations;
 #[cfg(test)]
 mod tests;
-#[cfg(test)]
+
+#[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-//#[cfg(feature = "runtime-benchmarks")]
-//mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
 
 // A multi-signature implementation for `Vane Payment System`
 
 #[frame_support::pallet]
 pub mod pallet {
-	use super::helper::{AccountSigners, CallExecuted, Confirm, ResolverChoice, RevertReasons};
-	use frame_support::{pallet, pallet_prelude::*, traits::tokens::currency::Currency};
+	use super::helper::{
+		AccountSigners, CallExecuted, Confirm, Dispute, DisputeVerdict, EscrowBackend, FaultRole,
+		HashLock, HistoryBounds, Milestone, PaymentAsset, PaymentStatus, Resolver, ResolverChoice,
+		ResolverInfo, RevertReasons, Ruling,
+	};
+	use frame_support::{
+		pallet,
+		pallet_prelude::*,
+		traits::{
+			tokens::{
+				currency::{Currency, ReservableCurrency},
+				fungibles,
+			},
+			OnUnbalanced, StorageVersion,
+		},
+	};
 	use frame_system::pallet_prelude::*;
+	use crate::weights::WeightInfo;
 	use vane_primitive::OrderTrait;
 	use sp_io::hashing::blake2_256;
 	use sp_runtime::{
 		parameter_types,
 		traits::{StaticLookup, TrailingZeroInput},
+		Perbill,
 	};
 	use sp_std::vec::Vec;
 
 
 	pub(super) type AccountOf<T> = <T as frame_system::Config>::AccountId;
 	pub(super) type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::Balance;
+	pub(super) type NegativeImbalanceOf<T> =
+		<<T as Config>::Currency as Currency<AccountOf<T>>>::NegativeImbalance;
+	pub(super) type AssetIdOf<T> =
+		<<T as Config>::Assets as fungibles::Inspect<AccountOf<T>>>::AssetId;
+
+	// Identifier issued by vane_pay for every escrowed payment
+	pub type PaymentId = u32;
 
 	// Max signers for Confirm Signers Bounded Vec
 	parameter_types! {
 		pub const MaxSigners: u16 = 2;
 	}
 
+	// Version 1 keyed the escrows by payment id, moved AccountMultiTxns into PayerHistory and
+	// PayeeHistory and the approved legal team to the Resolvers registry
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_balances::Config  {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		//type Order: OrderTrait + TypeInfo + Decode + Encode + Clone + PartialEq + Debug;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		// Where payments are held until settlement, MultiIdEscrow transfers them to the derived
+		// multi_id and ReserveEscrow keeps them reserved in the payer's account
+		type Escrow: EscrowBackend<Self>;
+
+		// Share of every settled payment taken by the protocol before paying the payee
+		#[pallet::constant]
+		type ProtocolFee: Get<Perbill>;
+
+		// Where the protocol fee goes, e.g. the treasury
+		type FeeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		// Assets other than the native currency which can be escrowed, e.g. pallet_assets. They
+		// are always held in the multi_id whatever the Escrow backend.
+		type Assets: fungibles::Inspect<Self::AccountId, Balance = BalanceOf<Self>>
+			+ fungibles::Mutate<Self::AccountId>
+			+ fungibles::Transfer<Self::AccountId>;
+
+		// Account receiving the protocol fee of payments made in Assets
+		type AssetFeeDestination: Get<Self::AccountId>;
+
+		// Number of blocks a payment can stay in the multi_id before it is refunded to the payer
+		#[pallet::constant]
+		type PaymentTimeout: Get<Self::BlockNumber>;
+
+		// Max number of payments that can expire in a single block
+		#[pallet::constant]
+		type MaxExpiringPerBlock: Get<u32>;
+
+		// Origin allowed to approve or remove legal team resolvers
+		type ResolverOrigin: EnsureOrigin<Self::Origin>;
+
+		// Max number of approved legal team resolvers
+		#[pallet::constant]
+		type MaxResolvers: Get<u32>;
+
+		// Bond a legal team account has to reserve before it is picked as a resolver
+		#[pallet::constant]
+		type ResolverBond: Get<BalanceOf<Self>>;
+
+		// Highest fee a legal team can advertise, as a share of the escrow it resolves
+		#[pallet::constant]
+		type MaxResolverFee: Get<Perbill>;
+
+		// Share of its bond a legal team loses when governance overturns its ruling on appeal,
+		// handed to FeeDestination
+		#[pallet::constant]
+		type ResolverSlash: Get<Perbill>;
+
+		// Number of blocks a legal team has to rule on a dispute before it is replaced
+		#[pallet::constant]
+		type RulingPeriod: Get<Self::BlockNumber>;
+
+		// Number of blocks the payer and the payee have to appeal a legal team ruling
+		#[pallet::constant]
+		type AppealPeriod: Get<Self::BlockNumber>;
+
+		// Origin which decides the outcome of a governance dispute, e.g. a council motion or a
+		// democracy referendum
+		type DisputeOrigin: EnsureOrigin<Self::Origin>;
+
+		// Deposit reserved from the account opening a dispute, lost to the other party when the
+		// verdict goes fully against it
+		#[pallet::constant]
+		type DisputeDeposit: Get<BalanceOf<Self>>;
+
+		// Max number of evidence hashes attached to a single dispute
+		#[pallet::constant]
+		type MaxEvidence: Get<u32>;
+
+		// Number of blocks a recorded fault keeps counting against an account
+		#[pallet::constant]
+		type FaultWindow: Get<Self::BlockNumber>;
+
+		// Max number of faults expiring in a single block, a fault recorded past it expires a
+		// block later
+		#[pallet::constant]
+		type MaxFaultsPerBlock: Get<u32>;
+
+		// Faults from which the payer has to reserve a deposit for every new payment
+		#[pallet::constant]
+		type DepositThreshold: Get<u32>;
+
+		// Deposit reserved per fault counted from DepositThreshold, returned when the payment
+		// is closed
+		#[pallet::constant]
+		type PenaltyDeposit: Get<BalanceOf<Self>>;
+
+		// Faults from which the account is barred from vane_pay and confirm_pay
+		#[pallet::constant]
+		type BarThreshold: Get<u32>;
+
+		// Number of blocks an account stays barred
+		#[pallet::constant]
+		type BarDuration: Get<Self::BlockNumber>;
+
+		// Faults above which every new fault slashes FaultPenalty from the account
+		#[pallet::constant]
+		type SlashThreshold: Get<u32>;
+
+		// Amount slashed from the free balance of the account on each fault above SlashThreshold,
+		// handed to FeeDestination
+		#[pallet::constant]
+		type FaultPenalty: Get<BalanceOf<Self>>;
+
+		// Max number of payees sharing a single payment
+		#[pallet::constant]
+		type MaxPayees: Get<u32>;
+
+		// Max number of milestones a payment can be released in
+		#[pallet::constant]
+		type MaxMilestones: Get<u32>;
+
+		// Max length of the reason code a payee can give when rejecting a payment
+		#[pallet::constant]
+		type MaxReasonLength: Get<u32>;
+
+		// Max length of the preimage revealed to claim a hash locked payment
+		#[pallet::constant]
+		type MaxPreimageLength: Get<u32>;
+
+		// Number of settled payments kept in the payer and in the payee history of an account,
+		// older ones are pruned
+		#[pallet::constant]
+		type MaxHistoryPerAccount: Get<u32>;
+
+		// Number of blocks the status and the rejection reason of a closed payment are kept
+		#[pallet::constant]
+		type StatusRetention: Get<Self::BlockNumber>;
+
+		// Max number of closed payments pruned, and of blocks checked for them, by on_initialize
+		// in a single block. Payments past it are pruned in the following blocks.
+		#[pallet::constant]
+		type MaxClosedPerBlock: Get<u32>;
+
+		// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
-	// Not yet implemented
+	// Bonded legal team accounts which can be chosen as a payment resolver, in turns
 	#[pallet::storage]
 	#[pallet::getter(fn get_resolver)]
-	pub(super) type ResolverSigner<T: Config> = StorageValue<_, T::AccountId>;
+	pub(super) type ResolverSigner<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxResolvers>, ValueQuery>;
 
-	// Number of multi-sig transactions done by a specific account_id
+	// Legal team accounts admitted by ResolverOrigin, with their bond and advertised fee
 	#[pallet::storage]
-	#[pallet::unbounded]
-	#[pallet::getter(fn get_account_multitxns)]
-	pub(super) type AccountMultiTxns<T: Config> =
-		StorageMap<_, Blake2_256, T::AccountId, Vec<CallExecuted<T>>, ValueQuery>;
+	#[pallet::getter(fn get_resolver_info)]
+	pub(super) type Resolvers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ResolverInfo<T>>;
+
+	// Fee of the legal team of a payment, fixed when the payment is made
+	#[pallet::storage]
+	#[pallet::getter(fn get_resolver_fee)]
+	pub(super) type ResolverFees<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, Perbill, ValueQuery>;
+
+	// Legal team which has to rule on an open dispute and the block it has to rule before
+	#[pallet::storage]
+	#[pallet::getter(fn get_dispute_resolver)]
+	pub(super) type DisputeResolvers<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, (T::AccountId, T::BlockNumber)>;
+
+	// Disputes whose legal team is replaced at the block if it has not ruled yet
+	#[pallet::storage]
+	#[pallet::getter(fn get_ruling_deadlines)]
+	pub(super) type RulingDeadlines<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<PaymentId, T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	// Legal team rulings waiting for the end of their appeal period
+	#[pallet::storage]
+	#[pallet::getter(fn get_ruling)]
+	pub(super) type Rulings<T: Config> = StorageMap<_, Twox64Concat, PaymentId, Ruling<T>>;
+
+	// Number of disputes a legal team has to rule on or whose ruling is not executed yet, its
+	// bond stays reserved until they are settled
+	#[pallet::storage]
+	#[pallet::getter(fn get_resolver_assignments)]
+	pub(super) type ResolverAssignments<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	// Index of the next legal team account to be assigned to a payment
+	#[pallet::storage]
+	#[pallet::getter(fn get_next_resolver)]
+	pub(super) type NextResolver<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	// Next payment id to be issued by vane_pay
+	#[pallet::storage]
+	#[pallet::getter(fn get_next_payment_id)]
+	pub(super) type NextPaymentId<T: Config> = StorageValue<_, PaymentId, ValueQuery>;
+
+	// Settled payments of an account as payer, keyed by a sequence number per account. Only the
+	// last MaxHistoryPerAccount records are kept.
+	#[pallet::storage]
+	#[pallet::getter(fn get_payer_history)]
+	pub(super) type PayerHistory<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		CallExecuted<T>,
+	>;
+
+	// Sequence numbers of the oldest kept and of the next record in PayerHistory
+	#[pallet::storage]
+	#[pallet::getter(fn get_payer_history_bounds)]
+	pub(super) type PayerHistoryBounds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, HistoryBounds, ValueQuery>;
+
+	// Settled payments of an account as payee, so merchants can list what they received
+	#[pallet::storage]
+	#[pallet::getter(fn get_payee_history)]
+	pub(super) type PayeeHistory<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		CallExecuted<T>,
+	>;
+
+	// Sequence numbers of the oldest kept and of the next record in PayeeHistory
+	#[pallet::storage]
+	#[pallet::getter(fn get_payee_history_bounds)]
+	pub(super) type PayeeHistoryBounds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, HistoryBounds, ValueQuery>;
 
 	// Introduced StorageMap because this storage should contain more  than one instance of
-	// AccountSigners
+	// AccountSigners. Keyed by payment id so a payer can have more than one open payment.
 
 	#[pallet::storage]
 	#[pallet::unbounded]
 	#[pallet::getter(fn get_allowed_signers)]
 	pub(super) type AllowedSigners<T: Config> =
-		StorageMap<_, Blake2_256, T::AccountId, AccountSigners<T>>;
+		StorageMap<_, Twox64Concat, PaymentId, AccountSigners<T>>;
 
-	// 1. Change confirmed signers to be StorageMap and key to be the payer's address
-	// 2. Change the way confirmed addresses are stored and make sure the payer starts
-	// 3. and clear the storage once the call is executed, clear multi_id account and confirmed and
-	// allowed signers
+	// Amount locked in the multi_id for each payment, only this amount is paid out on settlement
 	#[pallet::storage]
-	#[pallet::getter(fn get_signers)]
+	#[pallet::getter(fn get_locked_amount)]
+	pub(super) type LockedAmount<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, BalanceOf<T>>;
+
+	// Payees and their shares of a payment submitted with vane_pay_split. The first payee is the
+	// one AccountSigners and the multi_id are derived with.
+	#[pallet::storage]
+	#[pallet::getter(fn get_split_payees)]
+	pub(super) type SplitPayees<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		PaymentId,
+		BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPayees>,
+	>;
+
+	// Payees of a split payment who confirmed it, each one confirms independently
+	#[pallet::storage]
+	#[pallet::getter(fn get_split_confirmations)]
+	pub(super) type SplitConfirmations<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		PaymentId,
+		BoundedVec<T::AccountId, T::MaxPayees>,
+		ValueQuery,
+	>;
+
+	// Asset of every payment not made in the native currency
+	#[pallet::storage]
+	#[pallet::getter(fn get_payment_asset)]
+	pub(super) type PaymentAssets<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, PaymentAsset<AssetIdOf<T>>, ValueQuery>;
+
+	// Hash and timelock of a payment submitted with vane_pay_htlc
+	#[pallet::storage]
+	#[pallet::getter(fn get_hash_lock)]
+	pub(super) type HashLocks<T: Config> = StorageMap<_, Twox64Concat, PaymentId, HashLock<T>>;
+
+	// Commitment to the secret and the account of whoever can claim a payment submitted without
+	// a payee
+	#[pallet::storage]
+	#[pallet::getter(fn get_claim_commitment)]
+	pub(super) type ClaimCommitments<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, [u8; 32]>;
+
+	// Reason code given by the payee who rejected a payment, kept for StatusRetention blocks
+	// after the payment is closed
+	#[pallet::storage]
+	#[pallet::getter(fn get_rejection_reason)]
+	pub(super) type RejectionReasons<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, BoundedVec<u8, T::MaxReasonLength>>;
+
+	// Pending partial refund of a payment: who proposed it and the payee's share of the locked
+	// amount, the rest going back to the payer
+	#[pallet::storage]
+	#[pallet::getter(fn get_refund_proposal)]
+	pub(super) type RefundProposals<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, (T::AccountId, Perbill)>;
+
+	// Milestones of a payment submitted with vane_pay_milestones, each one released to the payee
+	// once both parties confirmed it
+	#[pallet::storage]
+	#[pallet::getter(fn get_milestones)]
+	pub(super) type Milestones<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, BoundedVec<Milestone<T>, T::MaxMilestones>>;
+
+	// Sum of the locked amounts of all open payments held by a multi_id. Anything the multi_id
+	// holds above this is dust or a stray deposit and is swept when the last payment closes.
+	#[pallet::storage]
+	#[pallet::getter(fn get_escrowed_total)]
+	pub(super) type EscrowedTotal<T: Config> =
+		StorageMap<_, Blake2_256, T::AccountId, BalanceOf<T>, ValueQuery>;
 
-	// Jibril change it to be storageMap and key to be multi_id,
-	// Introduce new stoarge value for multi_id created from Allowed Signers
+	// Confirmed addresses of a payment, payee first then payer. Cleared together with allowed
+	// signers once the call is executed or the payment is reverted.
+	#[pallet::storage]
+	#[pallet::getter(fn get_signers)]
 	pub(super) type ConfirmedSigners<T: Config> =
-		StorageMap<_,Twox64Concat, u32, BoundedVec<T::AccountId, MaxSigners>, ValueQuery>;
+		StorageMap<_, Twox64Concat, PaymentId, BoundedVec<T::AccountId, MaxSigners>, ValueQuery>;
 
 	// Number of reverted or faulty transaction a payer did
 	#[pallet::storage]
@@ -115,11 +438,90 @@ pub mod pallet {
 	pub(super) type RevertedTxnPayee<T: Config> =
 		StorageMap<_, Blake2_256, T::AccountId, u32, ValueQuery>;
 
+	// Current status of every payment issued by vane_pay, kept for StatusRetention blocks after
+	// the payment is closed
+	#[pallet::storage]
+	#[pallet::getter(fn get_payment_status)]
+	pub(super) type PaymentStatuses<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, PaymentStatus>;
+
+	// Closed payments whose status and rejection reason are removed from a given block on,
+	// consumed by on_initialize
+	#[pallet::storage]
+	#[pallet::getter(fn get_closed_payment)]
+	pub(super) type ClosedPayments<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, PaymentId, ()>;
+
+	// Oldest block whose closed payments may not all be pruned yet
+	#[pallet::storage]
+	#[pallet::getter(fn get_next_pruned_block)]
+	pub(super) type NextPrunedBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	// Faults to be taken off the counters at a given block, consumed by on_initialize
+	#[pallet::storage]
+	#[pallet::getter(fn get_fault_decay)]
+	pub(super) type FaultDecay<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(T::AccountId, FaultRole), T::MaxFaultsPerBlock>,
+		ValueQuery,
+	>;
+
+	// Block number until which an account cannot use vane_pay or confirm_pay
+	#[pallet::storage]
+	#[pallet::getter(fn get_barred_until)]
+	pub(super) type BarredUntil<T: Config> =
+		StorageMap<_, Blake2_256, T::AccountId, T::BlockNumber>;
+
+	// Penalty deposit reserved from the payer of a payment, returned when the payment is closed
+	#[pallet::storage]
+	#[pallet::getter(fn get_payment_deposit)]
+	pub(super) type PaymentDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, (T::AccountId, BalanceOf<T>)>;
+
+	// Block number at which the payment's escrow will expire
+	#[pallet::storage]
+	#[pallet::getter(fn get_payment_expiry)]
+	pub(super) type PaymentExpiry<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, T::BlockNumber>;
+
+	// Payments whose escrow expires at a given block, consumed by on_initialize
+	#[pallet::storage]
+	#[pallet::getter(fn get_expiring_payments)]
+	pub(super) type ExpiringPayments<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<PaymentId, T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	// Disputes waiting for a decision of the payment's resolver
+	#[pallet::storage]
+	#[pallet::getter(fn get_dispute)]
+	pub(super) type Disputes<T: Config> = StorageMap<_, Twox64Concat, PaymentId, Dispute<T>>;
+
+	// Payments whose payer asked for a ChangeOfDecision revert, waiting for the payee to agree
+	#[pallet::storage]
+	#[pallet::getter(fn get_revert_requests)]
+	pub(super) type RevertRequests<T: Config> =
+		StorageMap<_, Twox64Concat, PaymentId, RevertReasons>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		CallExecuted {
+			payment_id: PaymentId,
 			multi_id: T::AccountId,
+			amount: BalanceOf<T>,
+			fee: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		ProtocolFeeCharged {
+			payment_id: PaymentId,
+			fee: BalanceOf<T>,
 			timestamp: T::BlockNumber,
 		},
 
@@ -135,22 +537,313 @@ pub mod pallet {
 		},
 
 		PayeeAddressConfirmed {
+			payment_id: PaymentId,
 			account_id: T::AccountId,
 			timestamp: T::BlockNumber,
 		},
 
 		PayerAddressConfirmed {
+			payment_id: PaymentId,
 			account_id: T::AccountId,
 			timestamp: T::BlockNumber,
 		},
 
 		SubmittedPayment {
+			payment_id: PaymentId,
 			from_account: T::AccountId,
 			to_account: T::AccountId,
 			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+			resolver: Option<ResolverChoice>,
+			timestamp: T::BlockNumber,
+		},
+
+		SubmittedClaimablePayment {
+			payment_id: PaymentId,
+			from_account: T::AccountId,
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+			commitment: [u8; 32],
+			timestamp: T::BlockNumber,
+		},
+
+		PaymentClaimed {
+			payment_id: PaymentId,
+			payee: T::AccountId,
+			timestamp: T::BlockNumber,
+		},
+
+		SubmittedSplitPayment {
+			payment_id: PaymentId,
+			from_account: T::AccountId,
+			payees: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPayees>,
 			resolver: Option<ResolverChoice>,
 			timestamp: T::BlockNumber,
 		},
+
+		SplitShareReleased {
+			payment_id: PaymentId,
+			to_account: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		SubmittedMilestonePayment {
+			payment_id: PaymentId,
+			from_account: T::AccountId,
+			to_account: T::AccountId,
+			amount: BalanceOf<T>,
+			milestones: u32,
+			resolver: Option<ResolverChoice>,
+			timestamp: T::BlockNumber,
+		},
+
+		MilestoneConfirmed {
+			payment_id: PaymentId,
+			index: u32,
+			account_id: T::AccountId,
+			timestamp: T::BlockNumber,
+		},
+
+		MilestoneReleased {
+			payment_id: PaymentId,
+			index: u32,
+			to_account: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		SubmittedHashLockedPayment {
+			payment_id: PaymentId,
+			from_account: T::AccountId,
+			to_account: T::AccountId,
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+			hashlock: [u8; 32],
+			expires_at: T::BlockNumber,
+			timestamp: T::BlockNumber,
+		},
+
+		HashLockClaimed {
+			payment_id: PaymentId,
+			payee: T::AccountId,
+			preimage: BoundedVec<u8, T::MaxPreimageLength>,
+			timestamp: T::BlockNumber,
+		},
+
+		HashLockReclaimed {
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		PaymentCancelled {
+			payment_id: PaymentId,
+			multi_id: T::AccountId,
+			payer: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		PaymentRejected {
+			payment_id: PaymentId,
+			multi_id: T::AccountId,
+			payee: T::AccountId,
+			payer: T::AccountId,
+			amount: BalanceOf<T>,
+			reason: Option<BoundedVec<u8, T::MaxReasonLength>>,
+			timestamp: T::BlockNumber,
+		},
+
+		RefundProposed {
+			payment_id: PaymentId,
+			proposer: T::AccountId,
+			payee_share: Perbill,
+			timestamp: T::BlockNumber,
+		},
+
+		PartialRefundSettled {
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			payee: T::AccountId,
+			refunded: BalanceOf<T>,
+			payee_share: Perbill,
+			timestamp: T::BlockNumber,
+		},
+
+		RevertRequested {
+			payment_id: PaymentId,
+			payer: T::AccountId,
+			reason: RevertReasons,
+			timestamp: T::BlockNumber,
+		},
+
+		FundReverted {
+			payment_id: PaymentId,
+			multi_id: T::AccountId,
+			to_account: T::AccountId,
+			reason: RevertReasons,
+			timestamp: T::BlockNumber,
+		},
+
+		EscrowExpired {
+			payment_id: PaymentId,
+			multi_id: T::AccountId,
+			payer: T::AccountId,
+			payee: T::AccountId,
+			timestamp: T::BlockNumber,
+		},
+
+		// The refund of an expired escrow failed and was rolled back, None when it could not be
+		// retried in the next block either
+		ExpiryFailed {
+			payment_id: PaymentId,
+			error: DispatchError,
+			retry_at: Option<T::BlockNumber>,
+			timestamp: T::BlockNumber,
+		},
+
+		DustSwept {
+			multi_id: T::AccountId,
+			to_account: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		ResolverAdded {
+			account_id: T::AccountId,
+			timestamp: T::BlockNumber,
+		},
+
+		ResolverRemoved {
+			account_id: T::AccountId,
+			timestamp: T::BlockNumber,
+		},
+
+
+		DisputeRaised {
+			payment_id: PaymentId,
+			raised_by: T::AccountId,
+			reason: BoundedVec<u8, T::MaxReasonLength>,
+			deposit: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		EvidenceAdded {
+			payment_id: PaymentId,
+			account_id: T::AccountId,
+			evidence_hash: T::Hash,
+			timestamp: T::BlockNumber,
+		},
+
+		ResolverBonded {
+			account_id: T::AccountId,
+			bond: BalanceOf<T>,
+			fee: Perbill,
+			timestamp: T::BlockNumber,
+		},
+
+		RulingMade {
+			payment_id: PaymentId,
+			resolver: T::AccountId,
+			verdict: DisputeVerdict,
+			appeal_ends: T::BlockNumber,
+			timestamp: T::BlockNumber,
+		},
+
+		RulingAppealed {
+			payment_id: PaymentId,
+			appealed_by: T::AccountId,
+			timestamp: T::BlockNumber,
+		},
+
+		ResolverFeePaid {
+			payment_id: PaymentId,
+			resolver: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		ResolverSlashed {
+			payment_id: PaymentId,
+			account_id: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		// None when no other legal team could take over, governance then decides the dispute
+		ResolverReplaced {
+			payment_id: PaymentId,
+			replaced: T::AccountId,
+			resolver: Option<T::AccountId>,
+			deadline: Option<T::BlockNumber>,
+			timestamp: T::BlockNumber,
+		},
+
+		DisputeDepositForfeited {
+			payment_id: PaymentId,
+			from_account: T::AccountId,
+			to_account: T::AccountId,
+			amount: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		DisputeResolved {
+			payment_id: PaymentId,
+			multi_id: T::AccountId,
+			verdict: DisputeVerdict,
+			timestamp: T::BlockNumber,
+		},
+
+		PaymentStatusChanged {
+			payment_id: PaymentId,
+			previous: Option<PaymentStatus>,
+			status: PaymentStatus,
+			timestamp: T::BlockNumber,
+		},
+
+		FaultRecorded {
+			account_id: T::AccountId,
+			role: FaultRole,
+			faults: u32,
+			timestamp: T::BlockNumber,
+		},
+
+		DepositRaised {
+			account_id: T::AccountId,
+			deposit: BalanceOf<T>,
+			faults: u32,
+			timestamp: T::BlockNumber,
+		},
+
+		PenaltyDepositReserved {
+			payment_id: PaymentId,
+			account_id: T::AccountId,
+			deposit: BalanceOf<T>,
+			timestamp: T::BlockNumber,
+		},
+
+		AccountBarred {
+			account_id: T::AccountId,
+			until: T::BlockNumber,
+			faults: u32,
+			timestamp: T::BlockNumber,
+		},
+
+		FaultPenaltySlashed {
+			account_id: T::AccountId,
+			amount: BalanceOf<T>,
+			faults: u32,
+			timestamp: T::BlockNumber,
+		},
+
+		FaultExpired {
+			account_id: T::AccountId,
+			role: FaultRole,
+			faults: u32,
+			timestamp: T::BlockNumber,
+		},
 	}
 
 	#[pallet::error]
@@ -177,20 +870,188 @@ pub mod pallet {
 		NotAllowedPayeeOrPaymentNotInitialized,
 
 		MultiSigCallFailed,
+
+		NotAllowedToRevert,
+
+		WaitForPayerToRequestRevert,
+
+		RevertAlreadyRequested,
+
+		RevertCallFailed,
+
+		TooManyExpiringPayments,
+
+		NoResolverAvailable,
+
+		ResolverAlreadyExists,
+
+		ResolverNotFound,
+
+		// The legal team still has disputes to rule on or rulings waiting to be executed
+		ResolverHasAssignments,
+
+		TooManyResolvers,
+
+		UnsupportedResolver,
+
+		NotAllowedToRelease,
+
+		NotAllowedToDispute,
+
+		DisputeAlreadyRaised,
+
+		NoDisputeRaised,
+
+		NoDisputeResolver,
+
+		NotAllowedToResolve,
+
+		TooMuchEvidence,
+
+		// The account has not been admitted as a resolver by ResolverOrigin
+		NotAdmittedResolver,
+
+		ResolverFeeTooHigh,
+
+		TooManyRulingDeadlines,
+
+		NoRuling,
+
+		// Governance only decides a dispute the legal team ruled on when the ruling is appealed
+		RulingNotAppealed,
+
+		// Governance does not decide a dispute while a legal team is within its RulingPeriod
+		LegalTeamStillRuling,
+
+		// The ruling was appealed already, it can neither be appealed again nor be executed
+		AlreadyAppealed,
+
+		AppealPeriodOver,
+
+		AppealPeriodNotOver,
+
+		PaymentIdOverflow,
+
+		PaymentNotFound,
+
+		NotPaymentParticipant,
+
+		AmountBelowMinimum,
+
+		EscrowAccountingFailed,
+
+		FeeChargeFailed,
+
+		AccountBarred,
+
+		InvalidStatusTransition,
+
+		PaymentDisputed,
+
+		NoPayees,
+
+		DuplicatePayee,
+
+		// Rejections, partial refunds and reverts agreed by a single payee are not available on
+		// split payments, they go through a dispute
+		SplitPaymentNotSupported,
+
+		AmountOverflow,
+
+		NoMilestones,
+
+		NotMilestonePayment,
+
+		MilestoneNotFound,
+
+		MilestoneAlreadyConfirmed,
+
+		MilestoneAlreadyReleased,
+
+		ConfirmByMilestone,
+
+		NotAllowedToCancel,
+
+		// The payee already confirmed, the payment can only be reverted with revert_fund
+		UseRevertFund,
+
+		NotAllowedToReject,
+
+		InvalidTimelock,
+
+		NotHashLocked,
+
+		// The payment is hash locked, the payee claims it with claim_htlc
+		ClaimWithPreimage,
+
+		InvalidPreimage,
+
+		TimelockExpired,
+
+		TimelockNotExpired,
+
+		// Either a payee or a claim commitment must be given, not both
+		PayeeOrCommitment,
+
+		// A claimable payment is settled with its secret, disputes cannot apply to it
+		ResolverNotAllowed,
+
+		NotClaimable,
+
+		// The payment is claimable, the claimant settles it with claim_payment
+		ClaimWithSecret,
+
+		InvalidSecret,
+
+		// The payer gets a claimable payment back when its escrow expires
+		ClaimableUntilExpiry,
+
+		// The payee's share of a partial refund must be above zero and below the whole amount
+		InvalidRefundSplit,
+
+		NoRefundProposal,
+
+		CannotAcceptOwnProposal,
+
+		// The proposal was replaced before being accepted
+		RefundProposalChanged,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		// Refund every escrow which has not been settled within PaymentTimeout blocks, take
+		// faults older than FaultWindow off the counters and replace legal teams which did not
+		// rule within RulingPeriod blocks
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expired = Self::refund_expired_payments(now);
+			let decayed = Self::decay_faults(now);
+			let replaced = Self::replace_late_resolvers(now);
+			let pruned = Self::prune_closed_payments(now);
+
+			T::WeightInfo::refund_expired_payments(expired)
+				.saturating_add(T::WeightInfo::decay_faults(decayed))
+				.saturating_add(T::WeightInfo::replace_late_resolvers(replaced))
+				.saturating_add(T::WeightInfo::prune_closed_payments(pruned))
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		// A call that transfers fund from a buyer to a multi-owned account.
-		//
-
-		// Tasks:
-		// 1. Add filter to the call so that only an origin from AccountSigner can be an origin
-		#[pallet::weight(10)]
+		// The funds are in `asset`, PaymentAsset::Native for the native currency.
+		// Without a payee the payment is claimable: whoever reveals the secret committed to in
+		// `commitment` = blake2_256((secret, account)) and signs with that account gets it.
+		#[pallet::weight(T::WeightInfo::vane_pay())]
 		pub fn vane_pay(
 			origin: OriginFor<T>,
 			payee: Option<T::AccountId>,
+			commitment: Option<[u8; 32]>,
 			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
 			resolver: ResolverChoice,
 			// Third parameter will be a type that implements Order trait from primitive
 			//order: Option<T::Order>
@@ -198,36 +1059,288 @@ pub mod pallet {
 			// 1. Check if the Payee is in the Register Storage
 			// 2.
 			let payer = ensure_signed(origin)?;
-			let payee = payee.ok_or(Error::<T>::UnexpectedError)?;
+			Self::ensure_not_barred(&payer)?;
 
+			let payee = match (payee, commitment) {
+				(Some(payee), None) => payee,
+				(None, Some(commitment)) => {
+					ensure!(resolver == ResolverChoice::None, Error::<T>::ResolverNotAllowed);
+					let payment_id =
+						Self::inner_vane_pay_claimable(payer.clone(), commitment, amount, asset)?;
 
-			match resolver {
-				ResolverChoice::None => {
-					Self::inner_vane_pay_wo_resolver(payer.clone(), payee.clone(), amount)?;
 					let time = <frame_system::Pallet<T>>::block_number();
 
-					Self::deposit_event(Event::SubmittedPayment {
+					Self::deposit_event(Event::SubmittedClaimablePayment {
+						payment_id,
 						from_account: payer,
-						to_account: payee,
 						amount,
-						resolver: None,
+						asset,
+						commitment,
 						timestamp: time,
-					})
+					});
+
+					return Ok(())
 				},
-				_ => (),
-			}
+				_ => return Err(Error::<T>::PayeeOrCommitment.into()),
+			};
+
+			let (payment_id, resolver) = match resolver {
+				ResolverChoice::None => (
+					Self::inner_vane_pay_wo_resolver(payer.clone(), payee.clone(), amount, asset)?,
+					None,
+				),
+				ResolverChoice::LegalTeam => (
+					Self::inner_vane_pay_with_legal_team(
+						payer.clone(),
+						payee.clone(),
+						amount,
+						asset,
+					)?,
+					Some(ResolverChoice::LegalTeam),
+				),
+				ResolverChoice::Governance => (
+					Self::inner_vane_pay_with_governance(
+						payer.clone(),
+						payee.clone(),
+						amount,
+						asset,
+					)?,
+					Some(ResolverChoice::Governance),
+				),
+			};
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::SubmittedPayment {
+				payment_id,
+				from_account: payer,
+				to_account: payee,
+				amount,
+				asset,
+				resolver,
+				timestamp: time,
+			});
 
 			Ok(())
 		}
 
+		// Same as vane_pay, but the amount locked is the sum of the shares and each payee gets his
+		// share on settlement. Every payee confirms independently before the payer does.
+		#[pallet::weight(T::WeightInfo::vane_pay_split(payees.len() as u32))]
+		pub fn vane_pay_split(
+			origin: OriginFor<T>,
+			payees: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxPayees>,
+			resolver: ResolverChoice,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			Self::ensure_not_barred(&payer)?;
+
+			let payment_id =
+				Self::inner_vane_pay_split(payer.clone(), payees.clone(), resolver.clone())?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::SubmittedSplitPayment {
+				payment_id,
+				from_account: payer,
+				payees,
+				resolver: if resolver == ResolverChoice::None { None } else { Some(resolver) },
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Same as vane_pay, but the amount locked is the sum of the milestones and each milestone
+		// is released on its own with confirm_milestone. The rest stays locked in the multi_id.
+		#[pallet::weight(T::WeightInfo::vane_pay_milestones(milestones.len() as u32))]
+		pub fn vane_pay_milestones(
+			origin: OriginFor<T>,
+			payee: T::AccountId,
+			milestones: BoundedVec<(BalanceOf<T>, T::Hash), T::MaxMilestones>,
+			resolver: ResolverChoice,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			Self::ensure_not_barred(&payer)?;
+
+			let count = milestones.len() as u32;
+			let (payment_id, amount) = Self::inner_vane_pay_milestones(
+				payer.clone(),
+				payee.clone(),
+				milestones,
+				resolver.clone(),
+			)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::SubmittedMilestonePayment {
+				payment_id,
+				from_account: payer,
+				to_account: payee,
+				amount,
+				milestones: count,
+				resolver: if resolver == ResolverChoice::None { None } else { Some(resolver) },
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Same as vane_pay, but instead of being confirmed the payment is claimed by the payee
+		// with the preimage of `hashlock` (blake2_256) before `timelock` blocks have passed. After
+		// that the payer can take it back with reclaim_htlc. No resolver is involved.
+		#[pallet::weight(T::WeightInfo::vane_pay_htlc())]
+		pub fn vane_pay_htlc(
+			origin: OriginFor<T>,
+			payee: T::AccountId,
+			amount: BalanceOf<T>,
+			asset: PaymentAsset<AssetIdOf<T>>,
+			hashlock: [u8; 32],
+			timelock: T::BlockNumber,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			Self::ensure_not_barred(&payer)?;
+
+			let (payment_id, expires_at) = Self::inner_vane_pay_htlc(
+				payer.clone(),
+				payee.clone(),
+				amount,
+				asset,
+				hashlock,
+				timelock,
+			)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::SubmittedHashLockedPayment {
+				payment_id,
+				from_account: payer,
+				to_account: payee,
+				amount,
+				asset,
+				hashlock,
+				expires_at,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// The payee of a hash locked payment claims it by revealing the preimage, settling the
+		// payment. A barred payee can still claim, otherwise the payer would reclaim the funds
+		// once the timelock passed.
+		#[pallet::weight(T::WeightInfo::claim_htlc())]
+		pub fn claim_htlc(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			preimage: BoundedVec<u8, T::MaxPreimageLength>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(&caller == allowed_signers.get_payee(), Error::<T>::NotPaymentParticipant);
+
+			Self::inner_claim_htlc(payment_id, allowed_signers, &preimage)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::HashLockClaimed {
+				payment_id,
+				payee: caller,
+				preimage,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// The payer of a hash locked payment takes it back once the timelock has expired
+		#[pallet::weight(T::WeightInfo::reclaim_htlc())]
+		pub fn reclaim_htlc(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(&caller == allowed_signers.get_payer(), Error::<T>::NotPaymentParticipant);
+
+			let amount = Self::inner_reclaim_htlc(payment_id, allowed_signers)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::HashLockReclaimed {
+				payment_id,
+				payer: caller,
+				amount,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Claims a payment submitted without a payee, the caller becomes its payee and the
+		// payment is settled. A barred account can still claim, the payer already committed the
+		// funds to whoever knows the secret.
+		#[pallet::weight(T::WeightInfo::claim_payment())]
+		pub fn claim_payment(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			secret: [u8; 32],
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+
+			Self::inner_claim_payment(payment_id, allowed_signers, caller.clone(), secret)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::PaymentClaimed {
+				payment_id,
+				payee: caller,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Payer or payee confirms a milestone of a payment. Once both did, the milestone's amount
+		// goes to the payee and the last one settles the payment.
+		#[pallet::weight(T::WeightInfo::confirm_milestone())]
+		pub fn confirm_milestone(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			index: u32,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_not_barred(&caller)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(
+				&caller == allowed_signers.get_payer() || &caller == allowed_signers.get_payee(),
+				Error::<T>::NotPaymentParticipant
+			);
+			Self::ensure_not_disputed(payment_id)?;
+
+			Self::inner_confirm_milestone(payment_id, caller, allowed_signers, index)
+		}
+
 		// Get the confirm account address and store them in Signers Storage Item. Sort and make
 		// sure buyer's address is first
 		// Always make sure if its the buyer, he should be first in the vector,
 		// 		1. Store the account_id in the Signer Storage Item,
 		// 		2. Then next steps will follow after this,
 
-		#[pallet::weight(10)]
-		pub fn confirm_pay(origin: OriginFor<T>, who: Confirm, reference_no: u32) -> DispatchResult {
+		// Charged for MaxPayees payees upfront, the unused part is refunded for payments with
+		// fewer payees
+		#[pallet::weight(T::WeightInfo::confirm_pay(T::MaxPayees::get()))]
+		pub fn confirm_pay(
+			origin: OriginFor<T>,
+			who: Confirm,
+			payment_id: PaymentId,
+		) -> DispatchResultWithPostInfo {
 			// 1. Check if 0 index is a occupied and if true check if its a Payee if true return Err
 			// 2. If its not a Payee then add new account which it will be a Payer
 			// 3. If index 0 is not occupied then check if the address is a Payer, if its true
@@ -237,8 +1350,30 @@ pub mod pallet {
 			// confirm first
 
 			let user_account = ensure_signed(origin)?;
+			Self::ensure_not_barred(&user_account)?;
+
+			// The payment must have been issued by vane_pay and the caller must hold the role it
+			// confirms as, otherwise anyone, the payer included, could take the payee's slot
+			let issued_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			let holds_role = match who {
+				Confirm::Payee => Self::is_payee(payment_id, &issued_signers, &user_account),
+				Confirm::Payer => &user_account == issued_signers.get_payer(),
+			};
+			ensure!(holds_role, Error::<T>::NotPaymentParticipant);
+			Self::ensure_not_disputed(payment_id)?;
+			ensure!(!Milestones::<T>::contains_key(payment_id), Error::<T>::ConfirmByMilestone);
+			ensure!(!HashLocks::<T>::contains_key(payment_id), Error::<T>::ClaimWithPreimage);
+			ensure!(!ClaimCommitments::<T>::contains_key(payment_id), Error::<T>::ClaimWithSecret);
+
+			if let Some(payees) = SplitPayees::<T>::get(payment_id) {
+				let count = payees.len() as u32;
+				Self::confirm_split_pay(who, payment_id, user_account, issued_signers, payees)?;
+				return Ok(Some(T::WeightInfo::confirm_pay(count)).into())
+			}
+
 			// Check the storage
-			let b_vec = ConfirmedSigners::<T>::get(reference_no);
+			let b_vec = ConfirmedSigners::<T>::get(payment_id);
 
 
 			if let Some(addr) = b_vec.get(0) {
@@ -248,51 +1383,46 @@ pub mod pallet {
 					// Else for checking if payee tries to confirm twice.
 				} else {
 
-					ConfirmedSigners::<T>
-						::try_mutate(reference_no, |vec| vec.try_push(user_account.clone()))
+					ConfirmedSigners::<T>::try_mutate(payment_id, |vec| {
+						vec.try_push(user_account.clone())
+					})
 						.map_err(|_| Error::<T>::ExceededSigners)?;
 
 					let time = <frame_system::Pallet<T>>::block_number();
 
 					Self::deposit_event(Event::PayerAddressConfirmed {
+						payment_id,
 						account_id: user_account,
 						timestamp: time,
 					});
 
-					// Construct AccountSigner object from ConfirmedSigners storage
-
-					let confirmed_acc_signers = AccountSigners::<T>::new(
-						ConfirmedSigners::<T>::get(reference_no)
-							.get(0)
-							.ok_or(Error::<T>::UnexpectedError)?
-							.clone(),
-
-						ConfirmedSigners::<T>::get(reference_no)
-							.get(1)
-							.ok_or(Error::<T>::UnexpectedError)?
-							.clone(),
-						// The default resolver is none but logic will be made to be customizable
-						None,
-					);
-
-					// Derive the multi_id of newly constructed AccountSigner and one from
-					// AllowedSigners
-					let confirmed_multi_id = Self::derive_multi_id(confirmed_acc_signers);
-
 					// Get the AllowedSigners from storage
-					let payer = ConfirmedSigners::<T>::get(reference_no)
+					let payer = ConfirmedSigners::<T>::get(payment_id)
 						.get(1)
 						.ok_or(Error::<T>::UnexpectedError)?
 						.clone();
 
-					let payee = ConfirmedSigners::<T>::get(reference_no)
+					let payee = ConfirmedSigners::<T>::get(payment_id)
 						.get(0)
 						.ok_or(Error::<T>::UnexpectedError)?
 						.clone();
 
-					let allowed_signers = AllowedSigners::<T>::get(payer.clone())
+					let allowed_signers = AllowedSigners::<T>::get(payment_id)
 						.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
 
+					// Construct AccountSigner object from ConfirmedSigners storage, the resolver
+					// and the nonce are the ones of the submitted payment
+					let confirmed_acc_signers = AccountSigners::<T>::new(
+						payee.clone(),
+						payer.clone(),
+						allowed_signers.get_resolver().clone(),
+					)
+					.with_nonce(allowed_signers.get_nonce());
+
+					// Derive the multi_id of newly constructed AccountSigner and one from
+					// AllowedSigners
+					let confirmed_multi_id = Self::derive_multi_id(confirmed_acc_signers);
+
 					let allowed_multi_id = Self::derive_multi_id(allowed_signers);
 					// Compute the hash of both multi_ids (proof)
 					if confirmed_multi_id.eq(&allowed_multi_id) {
@@ -304,6 +1434,7 @@ pub mod pallet {
 
 						Self::dispatch_transfer_call(
 							proof,
+							payment_id,
 							payer,
 							payee,
 							allowed_multi_id,
@@ -320,12 +1451,16 @@ pub mod pallet {
 					Confirm::Payer => return Err(Error::<T>::WaitForPayeeToConfirm.into()),
 
 					Confirm::Payee => {
-						ConfirmedSigners::<T>::try_mutate(reference_no,|vec| vec.try_push(user_account.clone()))
-							.map_err(|_| Error::<T>::ExceededSigners)?;
+						ConfirmedSigners::<T>::try_mutate(payment_id, |vec| {
+							vec.try_push(user_account.clone())
+						})
+						.map_err(|_| Error::<T>::ExceededSigners)?;
+						Self::set_status(payment_id, PaymentStatus::PayeeConfirmed)?;
 
 						let time = <frame_system::Pallet<T>>::block_number();
 
 						Self::deposit_event(Event::PayeeAddressConfirmed {
+							payment_id,
 							account_id: user_account,
 							timestamp: time,
 						});
@@ -333,16 +1468,544 @@ pub mod pallet {
 				};
 			};
 
-			Ok(())
+			Ok(Some(T::WeightInfo::confirm_pay(1)).into())
 		}
 
 		// If the payer accidently makes a mistake due to RevertReasons the funds can be refunded
 		// back Punishment will occur if the reason is personal.
+		//
+		// 1. WrongPayeeAddress: only the payer, and only if no payee confirmed this payment yet.
+		// Confirmations of other payments between the same accounts do not count.
+		// 2. ChangeOfDecision: the payer requests first, then the payee agrees and the refund is
+		// dispatched. The payer gets a fault recorded in RevertedTxnPayer.
+		// 3. PayeeMisbehaviour: only the legal team account stored in AccountSigners can sign it,
+		// on an open dispute. It is a RefundPayer ruling which can be appealed like any other, the
+		// payee gets a fault recorded in RevertedTxnPayee when it is executed.
+		// Milestones already released stay with the payee, only the rest is refunded.
 
 		// We should introduce some sort of limit for WrongAddress reason occurrence.
-		#[pallet::weight(10)]
-		pub fn revert_fund(origin: OriginFor<T>, reason: RevertReasons) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::revert_fund())]
+		pub fn revert_fund(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			reason: RevertReasons,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers = AllowedSigners::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			let payer = allowed_signers.get_payer().clone();
+			let payee = allowed_signers.get_payee().clone();
+
+			match reason {
+				RevertReasons::WrongPayeeAddress => {
+					ensure!(caller == payer, Error::<T>::NotAllowedToRevert);
+					Self::ensure_not_disputed(payment_id)?;
+					Self::ensure_not_locked_to_payee(payment_id)?;
+					// Both the status and the confirmations are those of this payment id
+					ensure!(
+						PaymentStatuses::<T>::get(payment_id) == Some(PaymentStatus::Created) &&
+							!Self::any_payee_confirmed(payment_id),
+						Error::<T>::PayeeAlreadyConfirmed
+					);
+				},
+
+				RevertReasons::ChangeOfDecision => {
+					Self::ensure_not_disputed(payment_id)?;
+					Self::ensure_not_split(payment_id)?;
+					// The payer only registers the request, the payee has to agree on it
+					if caller == payer {
+						ensure!(
+							!RevertRequests::<T>::contains_key(payment_id),
+							Error::<T>::RevertAlreadyRequested
+						);
+						RevertRequests::<T>::insert(payment_id, reason.clone());
+
+						let time = <frame_system::Pallet<T>>::block_number();
+						Self::deposit_event(Event::RevertRequested {
+							payment_id,
+							payer,
+							reason,
+							timestamp: time,
+						});
+
+						return Ok(())
+					}
+
+					ensure!(caller == payee, Error::<T>::NotAllowedToRevert);
+					ensure!(
+						RevertRequests::<T>::get(payment_id) ==
+							Some(RevertReasons::ChangeOfDecision),
+						Error::<T>::WaitForPayerToRequestRevert
+					);
+
+					Self::record_fault(&payer, FaultRole::Payer);
+				},
+
+				// The legal team never moves the funds alone, its decision goes through a ruling
+				RevertReasons::PayeeMisbehaviour => {
+					ensure!(
+						allowed_signers.get_legal_account() == Some(&caller),
+						Error::<T>::NotAllowedToRevert
+					);
+
+					return Self::record_ruling(payment_id, caller, DisputeVerdict::RefundPayer)
+				},
+			};
+
+			let multi_id = Self::derive_multi_id(allowed_signers);
+			Self::dispatch_refund_call(payment_id, payer, multi_id, reason)
+		}
+
+		// The payer takes the payment back before any payee confirmed it, e.g. after paying the
+		// wrong order. The full amount is refunded and no fault is recorded.
+		#[pallet::weight(T::WeightInfo::cancel_payment())]
+		pub fn cancel_payment(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			let payer = allowed_signers.get_payer().clone();
+			ensure!(caller == payer, Error::<T>::NotAllowedToCancel);
+			Self::ensure_not_disputed(payment_id)?;
+			ensure!(!Self::any_payee_confirmed(payment_id), Error::<T>::UseRevertFund);
+			Self::ensure_not_locked_to_payee(payment_id)?;
+
+			let amount = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			let multi_id = Self::derive_multi_id(allowed_signers);
+
+			Self::set_status(payment_id, PaymentStatus::Cancelled)?;
+			Self::refund_multi_id(payment_id, &payer, &multi_id)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::PaymentCancelled {
+				payment_id,
+				multi_id,
+				payer,
+				amount,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// The payee sends the payment back when the order cannot be fulfilled, optionally with a
+		// reason code. The payer is refunded and no fault is recorded.
+		#[pallet::weight(T::WeightInfo::reject_payment())]
+		pub fn reject_payment(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			reason: Option<BoundedVec<u8, T::MaxReasonLength>>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(&caller == allowed_signers.get_payee(), Error::<T>::NotAllowedToReject);
+			Self::ensure_not_disputed(payment_id)?;
+			Self::ensure_not_split(payment_id)?;
+
+			let payer = allowed_signers.get_payer().clone();
+			let amount = LockedAmount::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			let multi_id = Self::derive_multi_id(allowed_signers);
+
+			Self::set_status(payment_id, PaymentStatus::Refunded)?;
+			Self::refund_multi_id(payment_id, &payer, &multi_id)?;
+			if let Some(code) = reason.clone() {
+				RejectionReasons::<T>::insert(payment_id, code);
+			}
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::PaymentRejected {
+				payment_id,
+				multi_id,
+				payee: caller,
+				payer,
+				amount,
+				reason,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Payer or payee proposes to settle the payment with `payee_share` of the locked amount
+		// going to the payee and the rest back to the payer. A new proposal from either party
+		// replaces the pending one.
+		#[pallet::weight(T::WeightInfo::propose_refund())]
+		pub fn propose_refund(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			payee_share: Perbill,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			ensure!(
+				&caller == allowed_signers.get_payer() || &caller == allowed_signers.get_payee(),
+				Error::<T>::NotPaymentParticipant
+			);
+			Self::ensure_not_disputed(payment_id)?;
+			Self::ensure_not_split(payment_id)?;
+			ensure!(
+				!payee_share.is_zero() && payee_share < Perbill::one(),
+				Error::<T>::InvalidRefundSplit
+			);
+
+			RefundProposals::<T>::insert(payment_id, (caller.clone(), payee_share));
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::RefundProposed {
+				payment_id,
+				proposer: caller,
+				payee_share,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// The other party accepts the pending proposal, `payee_share` must match it. Both sides
+		// are paid at once and nobody is at fault.
+		#[pallet::weight(T::WeightInfo::accept_refund())]
+		pub fn accept_refund(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			payee_share: Perbill,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers =
+				AllowedSigners::<T>::get(payment_id).ok_or(Error::<T>::PaymentNotFound)?;
+			let payer = allowed_signers.get_payer().clone();
+			let payee = allowed_signers.get_payee().clone();
+			ensure!(caller == payer || caller == payee, Error::<T>::NotPaymentParticipant);
+			Self::ensure_not_disputed(payment_id)?;
+			Self::ensure_not_split(payment_id)?;
+
+			let (proposer, proposed_share) =
+				RefundProposals::<T>::get(payment_id).ok_or(Error::<T>::NoRefundProposal)?;
+			ensure!(caller != proposer, Error::<T>::CannotAcceptOwnProposal);
+			ensure!(payee_share == proposed_share, Error::<T>::RefundProposalChanged);
+
+			let refunded = Self::inner_accept_refund(payment_id, allowed_signers, payee_share)?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::PartialRefundSettled {
+				payment_id,
+				payer,
+				payee,
+				refunded,
+				payee_share,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// When the payer and payee disagree, the legal team resolver of the payment can rule that
+		// the funds go through to the payee. It is a ReleaseToPayee ruling on the open dispute,
+		// which can be appealed like any other and is applied by execute_ruling. The payer's fault
+		// is then recorded in RevertedTxnPayer.
+		// Refunding the payer is done through revert_fund with PayeeMisbehaviour reason.
+		#[pallet::weight(T::WeightInfo::release_fund())]
+		pub fn release_fund(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers = AllowedSigners::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			ensure!(
+				allowed_signers.get_legal_account() == Some(&caller),
+				Error::<T>::NotAllowedToRelease
+			);
+
+			Self::record_ruling(payment_id, caller, DisputeVerdict::ReleaseToPayee)
+		}
+
+		// Admit a legal team account to the registry, it is chosen as a payment resolver once
+		// it posted its bond with bond_resolver
+		#[pallet::weight(T::WeightInfo::add_resolver())]
+		pub fn add_resolver(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::ResolverOrigin::ensure_origin(origin)?;
+
+			ensure!(!Resolvers::<T>::contains_key(&account), Error::<T>::ResolverAlreadyExists);
+			Resolvers::<T>::insert(&account, ResolverInfo::new());
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::ResolverAdded { account_id: account, timestamp: time });
+
+			Ok(())
+		}
+
+		// An admitted legal team reserves ResolverBond, topping up a slashed bond, and sets the
+		// fee it charges as a share of the escrows it resolves. It is then chosen in turns.
+		#[pallet::weight(T::WeightInfo::bond_resolver())]
+		pub fn bond_resolver(origin: OriginFor<T>, fee: Perbill) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(fee <= T::MaxResolverFee::get(), Error::<T>::ResolverFeeTooHigh);
+
+			let bond = Resolvers::<T>::try_mutate(
+				&caller,
+				|maybe_info| -> Result<BalanceOf<T>, DispatchError> {
+					let info = maybe_info.as_mut().ok_or(Error::<T>::NotAdmittedResolver)?;
+					let top_up = T::ResolverBond::get().saturating_sub(info.bond);
+					T::Currency::reserve(&caller, top_up)?;
+					info.bond = info.bond.saturating_add(top_up);
+					info.fee = fee;
+					Ok(info.bond)
+				},
+			)?;
+			ResolverSigner::<T>::try_mutate(|resolvers| -> DispatchResult {
+				if !resolvers.contains(&caller) {
+					resolvers.try_push(caller.clone()).map_err(|_| Error::<T>::TooManyResolvers)?;
+				}
+				Ok(())
+			})?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::ResolverBonded {
+				account_id: caller,
+				bond,
+				fee,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Remove a legal team account and release its bond. It is refused while the legal team has
+		// disputes or rulings outstanding, as the bond answers for them. Disputes opened later on
+		// its payments go to the next legal team in turns.
+		#[pallet::weight(T::WeightInfo::remove_resolver())]
+		pub fn remove_resolver(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::ResolverOrigin::ensure_origin(origin)?;
+			ensure!(
+				ResolverAssignments::<T>::get(&account) == 0,
+				Error::<T>::ResolverHasAssignments
+			);
+
+			let info = Resolvers::<T>::take(&account).ok_or(Error::<T>::ResolverNotFound)?;
+			ResolverSigner::<T>::mutate(|resolvers| {
+				resolvers.retain(|resolver| resolver != &account)
+			});
+			T::Currency::unreserve(&account, info.bond);
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::ResolverRemoved { account_id: account, timestamp: time });
+
 			Ok(())
 		}
+
+		// Payer or payee of a payment with a resolver can open a dispute, reserving
+		// DisputeDeposit. Settlement is frozen until the resolver recorded in AccountSigners
+		// decides with resolve_dispute. The evidence is committed as hashes, e.g. of preimages
+		// noted in pallet_preimage, and both sides can add more with add_evidence.
+		#[pallet::weight(T::WeightInfo::open_dispute())]
+		pub fn open_dispute(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			reason: BoundedVec<u8, T::MaxReasonLength>,
+			evidence_hash: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers = AllowedSigners::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			ensure!(allowed_signers.get_resolver().is_some(), Error::<T>::NoDisputeResolver);
+			ensure!(
+				&caller == allowed_signers.get_payer() ||
+					Self::is_payee(payment_id, &allowed_signers, &caller),
+				Error::<T>::NotAllowedToDispute
+			);
+			ensure!(!Disputes::<T>::contains_key(payment_id), Error::<T>::DisputeAlreadyRaised);
+
+			Self::set_status(payment_id, PaymentStatus::Disputed)?;
+
+			let deposit = T::DisputeDeposit::get();
+			T::Currency::reserve(&caller, deposit)?;
+
+			let mut dispute = Dispute::new(caller.clone(), reason.clone(), deposit);
+			dispute
+				.evidence
+				.try_push((caller.clone(), evidence_hash))
+				.map_err(|_| Error::<T>::TooMuchEvidence)?;
+			Disputes::<T>::insert(payment_id, dispute);
+			// The legal team of the payment has RulingPeriod blocks to rule. If it was removed
+			// from the registry since, the next legal team rules instead.
+			if let Some(legal_account) = allowed_signers.get_legal_account() {
+				let resolver = if Resolvers::<T>::contains_key(legal_account) {
+					Some(legal_account.clone())
+				} else {
+					Self::replacement_resolver(payment_id, &allowed_signers, legal_account)
+				};
+				if let Some(resolver) = resolver {
+					Self::assign_dispute_resolver(payment_id, resolver)?;
+				}
+			}
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::DisputeRaised {
+				payment_id,
+				raised_by: caller.clone(),
+				reason,
+				deposit,
+				timestamp: time,
+			});
+			Self::deposit_event(Event::EvidenceAdded {
+				payment_id,
+				account_id: caller,
+				evidence_hash,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Payer or payee attach another evidence hash to an open dispute, up to MaxEvidence for
+		// both sides together
+		#[pallet::weight(T::WeightInfo::add_evidence())]
+		pub fn add_evidence(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			evidence_hash: T::Hash,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers = AllowedSigners::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			ensure!(
+				&caller == allowed_signers.get_payer() ||
+					Self::is_payee(payment_id, &allowed_signers, &caller),
+				Error::<T>::NotAllowedToDispute
+			);
+
+			Disputes::<T>::try_mutate(payment_id, |maybe_dispute| -> DispatchResult {
+				let dispute = maybe_dispute.as_mut().ok_or(Error::<T>::NoDisputeRaised)?;
+				dispute
+					.evidence
+					.try_push((caller.clone(), evidence_hash))
+					.map_err(|_| Error::<T>::TooMuchEvidence)?;
+				Ok(())
+			})?;
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Self::deposit_event(Event::EvidenceAdded {
+				payment_id,
+				account_id: caller,
+				evidence_hash,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Dispatched by the resolver of the payment once it decided the outcome of a dispute.
+		// The ruling of the legal team assigned to the dispute is executed with execute_ruling
+		// once AppealPeriod passed without appeal. DisputeOrigin decides governance disputes,
+		// appealed rulings and the disputes no legal team is left to rule on. The funds in the
+		// multi_id go to the payee, back to the payer or get split.
+		#[pallet::weight(T::WeightInfo::resolve_dispute())]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			payment_id: PaymentId,
+			verdict: DisputeVerdict,
+		) -> DispatchResult {
+			let legal_team = match T::DisputeOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+			ensure!(Disputes::<T>::contains_key(payment_id), Error::<T>::NoDisputeRaised);
+
+			if let Some(caller) = legal_team {
+				return Self::record_ruling(payment_id, caller, verdict)
+			}
+
+			// The legal team earns its fee when its ruling is upheld and is slashed otherwise
+			let upheld_by = match Rulings::<T>::get(payment_id) {
+				Some(ruling) => {
+					ensure!(ruling.appealed, Error::<T>::RulingNotAppealed);
+					Rulings::<T>::remove(payment_id);
+					Self::release_assignment(&ruling.resolver);
+					if ruling.verdict == verdict {
+						Some(ruling.resolver)
+					} else {
+						Self::slash_resolver(payment_id, &ruling.resolver);
+						None
+					}
+				},
+				None => {
+					ensure!(
+						!DisputeResolvers::<T>::contains_key(payment_id),
+						Error::<T>::LegalTeamStillRuling
+					);
+					None
+				},
+			};
+
+			Self::settle_dispute(payment_id, verdict, upheld_by)
+		}
+
+		// The payer or a payee appeals the ruling of the legal team before AppealPeriod ends,
+		// the dispute then goes to DisputeOrigin
+		#[pallet::weight(T::WeightInfo::appeal_ruling())]
+		pub fn appeal_ruling(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let allowed_signers = AllowedSigners::<T>::get(payment_id)
+				.ok_or(Error::<T>::NotAllowedPayeeOrPaymentNotInitialized)?;
+			ensure!(
+				&caller == allowed_signers.get_payer() ||
+					Self::is_payee(payment_id, &allowed_signers, &caller),
+				Error::<T>::NotAllowedToDispute
+			);
+
+			let time = <frame_system::Pallet<T>>::block_number();
+
+			Rulings::<T>::try_mutate(payment_id, |maybe_ruling| -> DispatchResult {
+				let ruling = maybe_ruling.as_mut().ok_or(Error::<T>::NoRuling)?;
+				ensure!(!ruling.appealed, Error::<T>::AlreadyAppealed);
+				ensure!(time < ruling.appeal_ends, Error::<T>::AppealPeriodOver);
+				ruling.appealed = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RulingAppealed {
+				payment_id,
+				appealed_by: caller,
+				timestamp: time,
+			});
+
+			Ok(())
+		}
+
+		// Anyone executes a legal team ruling nobody appealed once AppealPeriod passed. The
+		// legal team is paid its fee from the escrow before the verdict is applied.
+		#[pallet::weight(T::WeightInfo::execute_ruling())]
+		pub fn execute_ruling(origin: OriginFor<T>, payment_id: PaymentId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let ruling = Rulings::<T>::get(payment_id).ok_or(Error::<T>::NoRuling)?;
+			ensure!(!ruling.appealed, Error::<T>::AlreadyAppealed);
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= ruling.appeal_ends,
+				Error::<T>::AppealPeriodNotOver
+			);
+			Rulings::<T>::remove(payment_id);
+			Self::release_assignment(&ruling.resolver);
+
+			Self::settle_dispute(payment_id, ruling.verdict, Some(ruling.resolver))
+		}
 	}
 }
@@ -0,0 +1,188 @@
+// Storage migrations of pallet-multisig-payment, run from on_runtime_upgrade
+
+pub mod v1 {
+	use crate::{
+		helper::{
+			AccountSigners, CallExecuted, HistoryBounds, PaymentStatus, Resolver, ResolverInfo,
+		},
+		AllowedSigners, Config, EscrowedTotal, ExpiringPayments, LockedAmount, NextPaymentId,
+		Pallet, PayeeHistory, PayeeHistoryBounds, PayerHistory, PayerHistoryBounds, PaymentExpiry,
+		PaymentId, PaymentStatuses, ResolverSigner, Resolvers,
+	};
+	use codec::{Decode, Encode};
+	use frame_support::{
+		migration::{clear_storage_prefix, get_storage_value, storage_iter},
+		pallet_prelude::*,
+		storage::{StorageDoubleMap as StorageDoubleMapT, StorageMap as StorageMapT},
+		traits::{Currency, GetStorageVersion, PalletInfoAccess},
+	};
+	use sp_runtime::traits::{Saturating, Zero};
+	use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+	// AccountSigners as stored before payment ids, keyed by the payer
+	#[derive(Encode, Decode)]
+	struct LegacyAccountSigners<T: Config> {
+		payee: T::AccountId,
+		payer: T::AccountId,
+		resolver: Option<Resolver<T>>,
+	}
+
+	// CallExecuted as recorded in AccountMultiTxns, before payment ids and amounts
+	#[derive(Encode, Decode)]
+	struct LegacyCallExecuted<T: Config> {
+		payer: T::AccountId,
+		payee: T::AccountId,
+		allowed_multi_id: T::AccountId,
+		confirmed_multi_id: T::AccountId,
+		proof: T::Hash,
+		time: T::BlockNumber,
+	}
+
+	// Migrates the storage of the pallet as released before payment ids to the current layout:
+	// the escrows keyed by their payer move under payment ids, the unbounded AccountMultiTxns
+	// history into PayerHistory and PayeeHistory and the approved legal team to the Resolvers
+	// registry
+	pub fn migrate<T: Config>() -> Weight {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let admitted = admit_approved_resolver::<T>();
+		let adopted = adopt_payer_keyed_escrows::<T>();
+		let moved = move_account_history::<T>();
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get()
+			.reads_writes(1, 1)
+			.saturating_add(admitted)
+			.saturating_add(adopted)
+			.saturating_add(moved)
+	}
+
+	// ResolverSigner held the single approved legal team account. It is admitted to Resolvers
+	// without bond, so it is left out of the rotation until it calls bond_resolver.
+	fn admit_approved_resolver<T: Config>() -> Weight {
+		let pallet = <Pallet<T>>::name().as_bytes();
+		let approved = get_storage_value::<T::AccountId>(pallet, b"ResolverSigner", b"");
+		ResolverSigner::<T>::kill();
+
+		match approved {
+			Some(resolver) => {
+				Resolvers::<T>::insert(resolver, ResolverInfo::new());
+				T::DbWeight::get().reads_writes(1, 2)
+			},
+			None => T::DbWeight::get().reads_writes(1, 1),
+		}
+	}
+
+	// Writes every AccountMultiTxns record to the history of its payer and of its payee, oldest
+	// first, keeping the newest MaxHistoryPerAccount of each account. The old records hold no
+	// payment id, amount or fee, they are recorded as legacy records without payment id. Records
+	// which no longer decode are dropped.
+	fn move_account_history<T: Config>() -> Weight {
+		let pallet = <Pallet<T>>::name().as_bytes();
+		let mut legacy: Vec<LegacyCallExecuted<T>> =
+			storage_iter::<Vec<LegacyCallExecuted<T>>>(pallet, b"AccountMultiTxns")
+				.flat_map(|(_, records)| records)
+				.collect();
+		let removed = clear_storage_prefix(pallet, b"AccountMultiTxns", b"", None, None);
+		legacy.sort_by_key(|record| record.time);
+
+		let mut by_payer: BTreeMap<T::AccountId, Vec<CallExecuted<T>>> = BTreeMap::new();
+		let mut by_payee: BTreeMap<T::AccountId, Vec<CallExecuted<T>>> = BTreeMap::new();
+		for legacy in legacy {
+			let record = CallExecuted::<T>::legacy(
+				legacy.payer.clone(),
+				legacy.payee.clone(),
+				legacy.allowed_multi_id,
+				legacy.confirmed_multi_id,
+				legacy.proof,
+				legacy.time,
+			);
+			by_payer.entry(legacy.payer).or_default().push(record.clone());
+			by_payee.entry(legacy.payee).or_default().push(record);
+		}
+
+		let written = write_history::<T, PayerHistory<T>, PayerHistoryBounds<T>>(by_payer)
+			.saturating_add(write_history::<T, PayeeHistory<T>, PayeeHistoryBounds<T>>(by_payee));
+
+		T::DbWeight::get().reads_writes(removed.unique as Weight, written)
+	}
+
+	// Stores the newest MaxHistoryPerAccount records of each account, numbered from 0
+	fn write_history<T: Config, History, Bounds>(
+		accounts: BTreeMap<T::AccountId, Vec<CallExecuted<T>>>,
+	) -> Weight
+	where
+		History: StorageDoubleMapT<T::AccountId, u32, CallExecuted<T>>,
+		Bounds: StorageMapT<T::AccountId, HistoryBounds>,
+	{
+		let mut written: Weight = 0;
+		for (who, records) in accounts {
+			let pruned = records.len().saturating_sub(T::MaxHistoryPerAccount::get() as usize);
+			let mut next: u32 = 0;
+			for record in records.into_iter().skip(pruned) {
+				History::insert(&who, next, record);
+				next = next.saturating_add(1);
+			}
+			Bounds::insert(&who, HistoryBounds { first: 0, next });
+			written = written.saturating_add(next as Weight).saturating_add(1);
+		}
+		written
+	}
+
+	// Escrows opened before payment ids were keyed by their payer. Each one still holding funds
+	// in its multi_id gets the next payment id and expires PaymentTimeout from now. Its nonce is
+	// left empty so its multi_id is still derived the old way and matches the account holding
+	// its funds. Confirmations were keyed by a caller supplied reference number which cannot be
+	// tied to an escrow, they are dropped and the parties confirm again. Legacy funds sit in the
+	// multi_id, so this expects MultiIdEscrow.
+	fn adopt_payer_keyed_escrows<T: Config>() -> Weight {
+		// Payment ids were issued already, the storage is keyed by them
+		if NextPaymentId::<T>::get() != 0 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let pallet = <Pallet<T>>::name().as_bytes();
+		let legacy: Vec<_> = storage_iter::<LegacyAccountSigners<T>>(pallet, b"AllowedSigners")
+			.map(|(_, signers)| signers)
+			.collect();
+
+		let mut cleared: Weight = 0;
+		for item in [&b"AllowedSigners"[..], b"ConfirmedSigners"] {
+			let removed = clear_storage_prefix(pallet, item, b"", None, None);
+			cleared = cleared.saturating_add(removed.unique as Weight);
+		}
+
+		let entries = legacy.len() as Weight;
+		let expiry =
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::PaymentTimeout::get());
+		let mut payment_id: PaymentId = 0;
+		for signers in legacy {
+			let accounts = AccountSigners::<T>::new(signers.payee, signers.payer, signers.resolver);
+			let multi_id = Pallet::<T>::derive_multi_id(accounts.clone());
+			let amount = T::Currency::free_balance(&multi_id);
+			if amount.is_zero() {
+				continue
+			}
+
+			AllowedSigners::<T>::insert(payment_id, accounts);
+			LockedAmount::<T>::insert(payment_id, amount);
+			EscrowedTotal::<T>::mutate(&multi_id, |total| *total = total.saturating_add(amount));
+			PaymentStatuses::<T>::insert(payment_id, PaymentStatus::Created);
+			if ExpiringPayments::<T>::try_mutate(expiry, |payments| payments.try_push(payment_id))
+				.is_ok()
+			{
+				PaymentExpiry::<T>::insert(payment_id, expiry);
+			}
+
+			payment_id = payment_id.saturating_add(1);
+		}
+		NextPaymentId::<T>::put(payment_id);
+
+		T::DbWeight::get().reads_writes(
+			entries.saturating_mul(3).saturating_add(1),
+			entries.saturating_mul(6).saturating_add(cleared).saturating_add(1),
+		)
+	}
+}
@@ -1,86 +1,178 @@
-use crate as pallet_multisig_payment;
-use frame_support::traits::{ConstU128, ConstU16, ConstU32, ConstU64};
-use frame_system as system;
-use pallet_balances;
-use sp_core::H256;
-use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
-};
+// Defines the mock runtime `Test` with the given escrow backend in the calling module, so the
+// same tests can run against every backend
+macro_rules! mock_runtime {
+	($escrow:ty) => {
+		use crate as pallet_multisig_payment;
+		use frame_support::{
+			parameter_types,
+			traits::{ConstU16, ConstU32, ConstU64, Currency, Hooks, OnUnbalanced},
+		};
+		use frame_system as system;
+		use pallet_balances;
+		use sp_core::H256;
+		use sp_runtime::{
+			testing::Header,
+			traits::{BlakeTwo256, IdentityLookup},
+			Perbill,
+		};
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
-type Block = frame_system::mocking::MockBlock<Test>;
+		type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+		type Block = frame_system::mocking::MockBlock<Test>;
 
-// Configure a mock runtime to test the pallet.
-frame_support::construct_runtime!(
-	pub enum Test where
-		Block = Block,
-		NodeBlock = Block,
-		UncheckedExtrinsic = UncheckedExtrinsic,
-	{
-		System: frame_system,
-		Balances: pallet_balances,
-		VanePayment: pallet_multisig_payment
-	}
-);
+		frame_support::construct_runtime!(
+			pub enum Test where
+				Block = Block,
+				NodeBlock = Block,
+				UncheckedExtrinsic = UncheckedExtrinsic,
+			{
+				System: frame_system,
+				Balances: pallet_balances,
+				Assets: pallet_assets,
+				VanePayment: pallet_multisig_payment
+			}
+		);
 
-impl system::Config for Test {
-	type BaseCallFilter = frame_support::traits::Everything;
-	type BlockWeights = ();
-	type BlockLength = ();
-	type Origin = Origin;
-	type Call = Call;
-	type Index = u64;
-	type BlockNumber = u64;
-	type Hash = H256;
-	type Hashing = BlakeTwo256;
-	type AccountId = u64;
-	type Lookup = IdentityLookup<Self::AccountId>;
-	type Header = Header;
-	type Event = Event;
-	type BlockHashCount = ConstU64<250>;
-	type DbWeight = ();
-	type Version = ();
-	type PalletInfo = PalletInfo;
-	type AccountData = pallet_balances::AccountData<Balance>;
-	type OnNewAccount = ();
-	type OnKilledAccount = ();
-	type SystemWeightInfo = ();
-	type SS58Prefix = ConstU16<42>;
-	type OnSetCode = ();
-	type MaxConsumers = ConstU32<16>;
-}
+		impl system::Config for Test {
+			type BaseCallFilter = frame_support::traits::Everything;
+			type BlockWeights = ();
+			type BlockLength = ();
+			type Origin = Origin;
+			type Call = Call;
+			type Index = u64;
+			type BlockNumber = u64;
+			type Hash = H256;
+			type Hashing = BlakeTwo256;
+			type AccountId = u64;
+			type Lookup = IdentityLookup<Self::AccountId>;
+			type Header = Header;
+			type Event = Event;
+			type BlockHashCount = ConstU64<250>;
+			type DbWeight = ();
+			type Version = ();
+			type PalletInfo = PalletInfo;
+			type AccountData = pallet_balances::AccountData<Balance>;
+			type OnNewAccount = ();
+			type OnKilledAccount = ();
+			type SystemWeightInfo = ();
+			type SS58Prefix = ConstU16<42>;
+			type OnSetCode = ();
+			type MaxConsumers = ConstU32<16>;
+		}
 
-pub type Balance = u64;
+		pub type Balance = u64;
 
-impl pallet_balances::Config for Test {
-	/// The type for recording an account's balance.
-	type Balance = Balance;
-	type DustRemoval = ();
-	/// The ubiquitous event type.
-	type Event = Event;
-	type ExistentialDeposit = ConstU64<500>;
-	type AccountStore = System;
-	type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
-	type MaxLocks = ConstU32<50>;
-	type MaxReserves = ();
-	type ReserveIdentifier = [u8; 8];
-}
+		impl pallet_balances::Config for Test {
+			/// The type for recording an account's balance.
+			type Balance = Balance;
+			type DustRemoval = ();
+			/// The ubiquitous event type.
+			type Event = Event;
+			type ExistentialDeposit = ConstU64<500>;
+			type AccountStore = System;
+			type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
+			type MaxLocks = ConstU32<50>;
+			type MaxReserves = ConstU32<50>;
+			type ReserveIdentifier = [u8; 8];
+		}
+
+		impl pallet_assets::Config for Test {
+			type Event = Event;
+			type Balance = Balance;
+			type AssetId = u32;
+			type Currency = Balances;
+			type ForceOrigin = frame_system::EnsureRoot<u64>;
+			type AssetDeposit = ConstU64<1>;
+			type AssetAccountDeposit = ConstU64<1>;
+			type MetadataDepositBase = ConstU64<1>;
+			type MetadataDepositPerByte = ConstU64<1>;
+			type ApprovalDeposit = ConstU64<1>;
+			type StringLimit = ConstU32<50>;
+			type Freezer = ();
+			type Extra = ();
+			type WeightInfo = ();
+		}
+
+		// Account receiving the protocol fee in tests
+		pub const TREASURY: u64 = 99;
+
+		parameter_types! {
+			pub static ProtocolFee: Perbill = Perbill::zero();
+			pub const MaxResolverFee: Perbill = Perbill::from_percent(10);
+			pub const ResolverSlash: Perbill = Perbill::from_percent(50);
+		}
 
-impl pallet_multisig_payment::Config for Test {
-	type Event = Event;
-	type Currency = Balances;
+		pub struct FeeToTreasury;
+		impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for FeeToTreasury {
+			fn on_nonzero_unbalanced(fee: pallet_balances::NegativeImbalance<Test>) {
+				Balances::resolve_creating(&TREASURY, fee);
+			}
+		}
+
+		impl pallet_multisig_payment::Config for Test {
+			type Event = Event;
+			type Currency = Balances;
+			type Escrow = $escrow;
+			type ProtocolFee = ProtocolFee;
+			type FeeDestination = FeeToTreasury;
+			type Assets = Assets;
+			type AssetFeeDestination = ConstU64<TREASURY>;
+			type PaymentTimeout = ConstU64<10>;
+			type MaxExpiringPerBlock = ConstU32<50>;
+			type ResolverOrigin = frame_system::EnsureRoot<u64>;
+			type MaxResolvers = ConstU32<10>;
+			type ResolverBond = ConstU64<5000>;
+			type MaxResolverFee = MaxResolverFee;
+			type ResolverSlash = ResolverSlash;
+			type RulingPeriod = ConstU64<5>;
+			type AppealPeriod = ConstU64<3>;
+			type DisputeOrigin = frame_system::EnsureRoot<u64>;
+			type DisputeDeposit = ConstU64<2000>;
+			type MaxEvidence = ConstU32<3>;
+			type FaultWindow = ConstU64<20>;
+			type MaxFaultsPerBlock = ConstU32<50>;
+			type DepositThreshold = ConstU32<2>;
+			type PenaltyDeposit = ConstU64<1000>;
+			type BarThreshold = ConstU32<3>;
+			type BarDuration = ConstU64<5>;
+			type SlashThreshold = ConstU32<3>;
+			type FaultPenalty = ConstU64<2000>;
+			type MaxPayees = ConstU32<3>;
+			type MaxMilestones = ConstU32<4>;
+			type MaxReasonLength = ConstU32<8>;
+			type MaxPreimageLength = ConstU32<32>;
+			type MaxHistoryPerAccount = ConstU32<3>;
+			type StatusRetention = ConstU64<30>;
+			type MaxClosedPerBlock = ConstU32<2>;
+			type WeightInfo = ();
+		}
+
+		// Build genesis storage according to the mock runtime.
+		pub fn new_test_ext() -> sp_io::TestExternalities {
+			let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+			pallet_balances::GenesisConfig::<Test> {
+				balances: vec![(1, 1_000_000), (2, 100_000), (3, 70000), (4, 120000), (5, 1000)],
+			}
+			.assimilate_storage(&mut t)
+			.unwrap();
+			let mut ext = sp_io::TestExternalities::new(t);
+			ext.execute_with(|| System::set_block_number(1));
+			ext
+		}
+
+		// Move the chain forward running VanePayment hooks on each block
+		pub fn run_to_block(n: u64) {
+			while System::block_number() < n {
+				System::set_block_number(System::block_number() + 1);
+				VanePayment::on_initialize(System::block_number());
+			}
+		}
+	};
 }
 
-// Build genesis storage according to the mock runtime.
-pub fn new_test_ext() -> sp_io::TestExternalities {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![(1, 1_000_000), (2, 100_000), (3, 70000), (4, 120000), (5, 1000)],
-	}
-	.assimilate_storage(&mut t)
-	.unwrap();
-	let mut ext = sp_io::TestExternalities::new(t);
-	ext.execute_with(|| System::set_block_number(1));
-	ext
+// Configure a mock runtime to test the pallet.
+mock_runtime!(crate::MultiIdEscrow);
+
+// The same runtime holding the escrowed funds in named reserves of the payer
+pub mod reserve_escrow {
+	mock_runtime!(crate::ReserveEscrow);
 }
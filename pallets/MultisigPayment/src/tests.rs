@@ -1,13 +1,21 @@
 use super::*;
 use crate::{
-	helper::{AccountSigners, Confirm, ResolverChoice},
+	helper::{
+		AccountSigners, Confirm, DisputeVerdict, FaultRole, HistoryBounds, PaymentAsset,
+		PaymentStatus, Resolver, ResolverChoice, ResolverInfo, RevertReasons,
+	},
 	mock::*,
 	Error,
 };
 use codec::{Decode, Encode};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{ConstU32, GetStorageVersion, ReservableCurrency, StorageVersion},
+	BoundedVec,
+};
+use sp_core::H256;
 use sp_io::hashing::blake2_256;
-use sp_runtime::traits::TrailingZeroInput;
+use sp_runtime::{traits::TrailingZeroInput, Perbill};
 
 // A testing Account Object
 pub fn new_acc(payee: u64, payer: u64) -> AccountSigners<Test> {
@@ -21,6 +29,162 @@ fn derive_multi_id_test() {
 	new_test_ext().execute_with(|| {
 		let acc = new_acc(2, 1);
 		assert_eq!(VanePayment::derive_multi_id(acc), 3149924236044933178);
+
+		// Every payment between the same accounts gets its own multi_id
+		let salted = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ne!(salted, 3149924236044933178);
+		assert_ne!(salted, VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(1))));
+
+		// and it differs from one chain to another
+		frame_system::BlockHash::<Test>::insert(0, sp_core::H256::repeat_byte(1));
+		assert_ne!(salted, VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0))));
+	})
+}
+
+// Escrows keyed by their payer move under payment ids and keep the unsalted multi_id holding
+// their funds
+#[test]
+fn v1_migration_moves_payer_keyed_escrows() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{
+			migration::{get_storage_value, put_storage_value},
+			StorageHasher, Twox64Concat,
+		};
+
+		// Escrows of payers 1 and 4 as stored before payment ids, keyed by the payer
+		let payer_1 = blake2_256(&1u64.encode());
+		let payer_4 = blake2_256(&4u64.encode());
+		put_storage_value(b"VanePayment", b"AllowedSigners", &payer_1, (2u64, 1u64, None::<u8>));
+		put_storage_value(
+			b"VanePayment",
+			b"AllowedSigners",
+			&payer_4,
+			(2u64, 4u64, Some(Resolver::<Test>::Governance)),
+		);
+		put_storage_value(
+			b"VanePayment",
+			b"ConfirmedSigners",
+			&Twox64Concat::hash(&0u32.encode()),
+			vec![2u64],
+		);
+		assert_ok!(Balances::transfer(Origin::signed(1), 3149924236044933178, 100000));
+		let multi_id_4 = VanePayment::derive_multi_id(new_acc(2, 4));
+		assert_ok!(Balances::transfer(Origin::signed(4), multi_id_4, 50000));
+		StorageVersion::new(0).put::<VanePayment>();
+
+		crate::migrations::v1::migrate::<Test>();
+
+		assert_eq!(VanePayment::on_chain_storage_version(), 1);
+		assert_eq!(VanePayment::get_next_payment_id(), 2);
+		let payment_of = |payer: u64| {
+			(0..2)
+				.find(|id| {
+					VanePayment::get_allowed_signers(id).map(|signers| *signers.get_payer())
+						== Some(payer)
+				})
+				.unwrap()
+		};
+		let (payment_1, payment_4) = (payment_of(1), payment_of(4));
+
+		assert_eq!(VanePayment::get_allowed_signers(payment_1), Some(new_acc(2, 1)));
+		assert_eq!(
+			VanePayment::derive_multi_id(VanePayment::get_allowed_signers(payment_1).unwrap()),
+			3149924236044933178
+		);
+		assert_eq!(VanePayment::get_locked_amount(payment_1), Some(100000));
+		assert_eq!(VanePayment::get_locked_amount(payment_4), Some(50000));
+		for payment_id in [payment_1, payment_4] {
+			assert_eq!(VanePayment::get_payment_status(payment_id), Some(PaymentStatus::Created));
+			assert_eq!(VanePayment::get_payment_expiry(payment_id), Some(11));
+		}
+		// Confirmations of the old reference numbers are not carried over
+		assert!(VanePayment::get_signers(0).is_empty());
+		assert_eq!(get_storage_value::<u64>(b"VanePayment", b"AllowedSigners", &payer_1), None);
+
+		// Both escrows settle under their payment id
+		for (payer, payment_id) in [(1, payment_1), (4, payment_4)] {
+			assert_ok!(VanePayment::revert_fund(
+				Origin::signed(payer),
+				payment_id,
+				RevertReasons::ChangeOfDecision
+			));
+			assert_ok!(VanePayment::revert_fund(
+				Origin::signed(2),
+				payment_id,
+				RevertReasons::ChangeOfDecision
+			));
+		}
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(Balances::free_balance(4), 120000);
+	})
+}
+
+// The approved legal team is admitted to the registry without bond
+#[test]
+fn v1_migration_admits_approved_legal_team() {
+	new_test_ext().execute_with(|| {
+		frame_support::migration::put_storage_value(b"VanePayment", b"ResolverSigner", b"", 3u64);
+		StorageVersion::new(0).put::<VanePayment>();
+
+		crate::migrations::v1::migrate::<Test>();
+
+		assert_eq!(VanePayment::get_resolver_info(3), Some(ResolverInfo::new()));
+		assert!(VanePayment::get_resolver().is_empty());
+		assert_eq!(VanePayment::on_chain_storage_version(), 1);
+	})
+}
+
+#[test]
+fn v1_migration_moves_account_history() {
+	new_test_ext().execute_with(|| {
+		use frame_support::migration::{get_storage_value, put_storage_value};
+
+		// Settlements as recorded in AccountMultiTxns, keyed by the payer
+		let record = |payer: u64, payee: u64, time: u64| {
+			(payer, payee, 7u64, 8u64, H256::repeat_byte(time as u8), time)
+		};
+		let payer_1 = blake2_256(&1u64.encode());
+		let payer_4 = blake2_256(&4u64.encode());
+		put_storage_value(
+			b"VanePayment",
+			b"AccountMultiTxns",
+			&payer_1,
+			vec![record(1, 2, 2), record(1, 3, 3), record(1, 2, 5), record(1, 2, 6)],
+		);
+		put_storage_value(b"VanePayment", b"AccountMultiTxns", &payer_4, vec![record(4, 2, 4)]);
+		StorageVersion::new(0).put::<VanePayment>();
+
+		crate::migrations::v1::migrate::<Test>();
+
+		let legacy =
+			|(payer, payee, allowed, confirmed, proof, time): (u64, u64, u64, u64, H256, u64)| {
+				CallExecuted::<Test>::legacy(payer, payee, allowed, confirmed, proof, time)
+			};
+		// A migrated record is told apart from one of payment 0 by its missing payment id
+		assert_ne!(
+			legacy(record(1, 2, 6)),
+			CallExecuted::<Test>::new(0, 1, 2, 0, 0, 7, 8, H256::repeat_byte(6), 6)
+		);
+		// Only the newest MaxHistoryPerAccount records of each account are kept
+		assert_eq!(
+			VanePayment::payer_history_page(&1, 0, 10),
+			(vec![legacy(record(1, 3, 3)), legacy(record(1, 2, 5)), legacy(record(1, 2, 6))], None)
+		);
+		assert_eq!(VanePayment::get_payer_history_bounds(1), HistoryBounds { first: 0, next: 3 });
+		assert_eq!(
+			VanePayment::payer_history_page(&4, 0, 10),
+			(vec![legacy(record(4, 2, 4))], None)
+		);
+		// The payee history merges the payers by time
+		assert_eq!(
+			VanePayment::payee_history_page(&2, 0, 10),
+			(vec![legacy(record(4, 2, 4)), legacy(record(1, 2, 5)), legacy(record(1, 2, 6))], None)
+		);
+		assert_eq!(VanePayment::payee_history_page(&3, 0, 10).0.len(), 1);
+		assert_eq!(
+			get_storage_value::<Vec<u8>>(b"VanePayment", b"AccountMultiTxns", &payer_1),
+			None
+		);
 	})
 }
 
@@ -34,13 +198,35 @@ fn multi_acc_formation_storage_test() {
 
 		// Checking the account storage in frame_system;
 		assert!(System::account_exists(&multi_id));
-		assert_eq!(System::consumers(&multi_id), 1);
 
-		// This should fail because the account is already registered;
-		assert_noop!(
-			VanePayment::create_multi_account(multi_id),
-			Error::<Test>::MultiAccountExists
-		);
+		// An account already registered is reused
+		assert_ok!(VanePayment::create_multi_account(multi_id));
+		assert!(System::account_exists(&multi_id));
+	})
+}
+
+// Funding the multi_id of the next payment ahead of it does not block the payment
+#[test]
+fn prefunded_multi_id_is_reused() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(Balances::transfer(Origin::signed(3), multi_id, 500));
+
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+		// The stray deposit is swept back to the payer
+		assert_eq!(Balances::free_balance(2), 200000);
+		assert_eq!(Balances::free_balance(1), 900500);
+		assert_eq!(Balances::free_balance(multi_id), 0);
 	})
 }
 
@@ -51,33 +237,81 @@ fn account_confirmation() {
 		// Configuring account storage;
 
 		// Vane Pay first
-		assert_ok!(VanePayment::vane_pay(Origin::signed(1), Some(2), 100000, ResolverChoice::None));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
 		// Payer and Payee confirmation;
 		// Payer confirmation first should fail
 		assert_noop!(
-			VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer),
+			VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0),
 			Error::<Test>::WaitForPayeeToConfirm
 		);
 		// Payee confirmation should work
-		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
 		// Payee re-confirmation should fail
 		assert_noop!(
-			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee),
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0),
 			Error::<Test>::PayeeAlreadyConfirmed
 		);
 		// Payer Confirmation
-		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
 
-		// Checking storage
-		assert_eq!(VanePayment::get_signers(), vec![2, 1]);
+		// Checking storage, the payment is cleared once the call is executed
+		assert_eq!(VanePayment::get_signers(0).len(), 0);
+		assert_eq!(VanePayment::get_allowed_signers(0), None);
 
-		// This should fail
+		assert_eq!(Balances::free_balance(2), 200000);
+	})
+}
+
+// Testing confirmation only works for payments issued by vane_pay
+#[test]
+fn confirm_pay_rejects_unknown_references() {
+	new_test_ext().execute_with(|| {
+		// Nothing has been issued yet
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0),
+			Error::<Test>::PaymentNotFound
+		);
+
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		System::assert_last_event(
+			crate::Event::SubmittedPayment {
+				payment_id: 0,
+				from_account: 1,
+				to_account: 2,
+				amount: 100000,
+				asset: PaymentAsset::Native,
+				resolver: None,
+				timestamp: 1,
+			}
+			.into(),
+		);
+
+		// Unrelated accounts cannot fill the slots of the payment
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(3), Confirm::Payee, 0),
+			Error::<Test>::NotPaymentParticipant
+		);
 		assert_noop!(
-			VanePayment::confirm_pay(Origin::signed(3), Confirm::Payer),
-			Error::<Test>::ExceededSigners
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 1),
+			Error::<Test>::PaymentNotFound
 		);
 
-		assert_eq!(Balances::free_balance(2), 199500);
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_eq!(VanePayment::get_signers(0), vec![2]);
 	})
 }
 
@@ -86,9 +320,9 @@ fn account_confirmation() {
 fn inner_vane_pay_wo_resolver_test() {
 	new_test_ext().execute_with(|| {
 		// Multi Account Id
-		let acc = new_acc(5, 1);
+		let acc = new_acc(5, 1).with_nonce(Some(0));
 		let multi_id = VanePayment::derive_multi_id(acc);
-		assert_ok!(VanePayment::inner_vane_pay_wo_resolver(1, 5, 100000));
+		assert_ok!(VanePayment::inner_vane_pay_wo_resolver(1, 5, 100000, PaymentAsset::Native));
 
 		// Checking Multi_Id balance
 		assert_eq!(Balances::free_balance(multi_id), 100000);
@@ -100,9 +334,9 @@ fn inner_vane_pay_wo_resolver_test() {
 #[test]
 fn multi_sig_single() {
 	new_test_ext().execute_with(|| {
-		let acc = new_acc(5, 1);
+		let acc = new_acc(5, 1).with_nonce(Some(0));
 		let multi_id = VanePayment::derive_multi_id(acc);
-		assert_ok!(VanePayment::inner_vane_pay_wo_resolver(1, 5, 100000));
+		assert_ok!(VanePayment::inner_vane_pay_wo_resolver(1, 5, 100000, PaymentAsset::Native));
 		// Check balance for payer
 		assert_eq!(Balances::free_balance(1), 900000);
 		// Check balance for payee
@@ -114,45 +348,2545 @@ fn multi_sig_single() {
 		let encoded_proof = (multi_id, multi_id).using_encoded(blake2_256);
 		let proof = Decode::decode(&mut TrailingZeroInput::new(encoded_proof.as_ref())).unwrap();
 
-		assert_ok!(VanePayment::dispatch_transfer_call(proof, 1, 5, multi_id, multi_id));
+		assert_ok!(VanePayment::dispatch_transfer_call(proof, 0, 1, 5, multi_id, multi_id));
 
 		// Check storage for call executed per id
-		assert_eq!(VanePayment::get_account_multitxns(1).len(), 1);
+		assert_eq!(VanePayment::payer_history_page(&1, 0, 10).0.len(), 1);
+		assert_eq!(VanePayment::payee_history_page(&5, 0, 10).0.len(), 1);
 		// Check balance for multi_id
-		assert_eq!(Balances::free_balance(multi_id), 500);
+		assert_eq!(Balances::free_balance(multi_id), 0);
 		// Check balance for payee
-		assert_eq!(Balances::free_balance(5), 100500);
+		assert_eq!(Balances::free_balance(5), 101000);
 		// Check balance for payer
 		assert_eq!(Balances::free_balance(1), 900000);
 	})
 }
 
+// Checking settlement pays exactly the locked amount and sweeps stray deposits
+#[test]
+fn settlement_transfers_exact_amount_and_sweeps_dust() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_eq!(VanePayment::get_escrowed_total(multi_id), 100000);
+
+		// A stray deposit to the multi_id is not paid to the payee, closing the payment sweeps it
+		// back to the payer
+		assert_ok!(Balances::transfer(Origin::signed(3), multi_id, 700));
+
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+		assert_eq!(Balances::free_balance(2), 200000);
+		assert_eq!(Balances::free_balance(1), 900700);
+		assert_eq!(Balances::free_balance(multi_id), 0);
+		assert_eq!(VanePayment::get_escrowed_total(multi_id), 0);
+		System::assert_has_event(
+			crate::Event::DustSwept { multi_id, to_account: 1, amount: 700, timestamp: 1 }.into(),
+		);
+	})
+}
+
+#[test]
+fn vane_pay_below_existential_deposit_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				499,
+				PaymentAsset::Native,
+				ResolverChoice::None
+			),
+			Error::<Test>::AmountBelowMinimum
+		);
+	})
+}
+
+#[test]
+fn minimum_payment_settles_after_protocol_fee() {
+	new_test_ext().execute_with(|| {
+		ProtocolFee::set(sp_runtime::Perbill::from_percent(10));
+
+		// 554 less its 55 fee would leave the multi_id below the existential deposit of 500
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				554,
+				PaymentAsset::Native,
+				ResolverChoice::None
+			),
+			Error::<Test>::AmountBelowMinimum
+		);
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			555,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+		assert_eq!(Balances::free_balance(2), 100500);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+	})
+}
+
+// Checking the protocol fee taken on settlement
+#[test]
+fn protocol_fee_charged_on_settlement() {
+	new_test_ext().execute_with(|| {
+		ProtocolFee::set(sp_runtime::Perbill::from_percent(1));
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+		assert_eq!(Balances::free_balance(2), 199000);
+		assert_eq!(Balances::free_balance(TREASURY), 1000);
+		assert_eq!(Balances::free_balance(multi_id), 0);
+		System::assert_has_event(
+			crate::Event::ProtocolFeeCharged { payment_id: 0, fee: 1000, timestamp: 1 }.into(),
+		);
+		System::assert_last_event(
+			crate::Event::CallExecuted {
+				payment_id: 0,
+				multi_id,
+				amount: 99000,
+				fee: 1000,
+				timestamp: 1,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn protocol_fee_not_charged_on_refund() {
+	new_test_ext().execute_with(|| {
+		ProtocolFee::set(sp_runtime::Perbill::from_percent(1));
+
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::revert_fund(
+			Origin::signed(1),
+			0,
+			RevertReasons::WrongPayeeAddress
+		));
+
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(Balances::free_balance(TREASURY), 0);
+	})
+}
+
+// Checking the reserve based escrow backend
+#[test]
+fn reserve_escrow_backend() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(<ReserveEscrow as EscrowBackend<Test>>::lock(0, &1, &multi_id, 100000));
+
+		// Funds stay with the payer, nothing is moved to the multi_id
+		assert_eq!(Balances::reserved_balance(1), 100000);
+		assert_eq!(Balances::free_balance(1), 900000);
+		assert_eq!(Balances::free_balance(multi_id), 0);
+
+		// Partial release to the payee then the rest back to the payer
+		assert_ok!(<ReserveEscrow as EscrowBackend<Test>>::release(
+			0, &1, &multi_id, &2, 40000, false
+		));
+		assert_eq!(Balances::free_balance(2), 140000);
+		assert_ok!(<ReserveEscrow as EscrowBackend<Test>>::release(
+			0, &1, &multi_id, &1, 60000, true
+		));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 960000);
+
+		// Nothing left to release
+		assert_noop!(
+			<ReserveEscrow as EscrowBackend<Test>>::release(0, &1, &multi_id, &2, 1, true),
+			Error::<Test>::EscrowAccountingFailed
+		);
+	})
+}
+
 // Checking dispatching transfer Call inside confirm_pay
 #[test]
 fn dispatch_transfer_in_confirm_pay() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(VanePayment::inner_vane_pay_wo_resolver(1, 2, 100000));
+		assert_ok!(VanePayment::inner_vane_pay_wo_resolver(1, 2, 100000, PaymentAsset::Native));
 		// confirm payee
-		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
 		// confirm wrong payer should fail
 		assert_noop!(
-			VanePayment::confirm_pay(Origin::signed(3), Confirm::Payer),
-			Error::<Test>::NotAllowedPayeeOrPaymentNotInitialized
+			VanePayment::confirm_pay(Origin::signed(3), Confirm::Payer, 0),
+			Error::<Test>::NotPaymentParticipant
 		);
 		// confirm payer
-		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
 
 		// Check payee balance
-		assert_eq!(Balances::free_balance(2), 199500);
+		assert_eq!(Balances::free_balance(2), 200000);
 	})
 }
 
 // Checking multi-sig call for a seller.
 
+// Checking one payer keeping several open payments with the same payee, each in its own multi_id
+#[test]
+fn multiple_open_payments_per_payer() {
+	new_test_ext().execute_with(|| {
+		let first = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		let second = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(1)));
+		assert_ne!(first, second);
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			50000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(4),
+			None,
+			30000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		assert_eq!(VanePayment::get_next_payment_id(), 3);
+		assert_eq!(VanePayment::get_allowed_signers(0), Some(new_acc(2, 1).with_nonce(Some(0))));
+		assert_eq!(VanePayment::get_allowed_signers(1), Some(new_acc(2, 1).with_nonce(Some(1))));
+		assert_eq!(VanePayment::get_allowed_signers(2), Some(new_acc(4, 1).with_nonce(Some(2))));
+		assert_eq!(Balances::free_balance(first), 100000);
+		assert_eq!(Balances::free_balance(second), 50000);
+
+		// Settling the second payment does not touch the first one
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 1));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 1));
+		assert_eq!(Balances::free_balance(2), 150000);
+		assert_eq!(Balances::free_balance(second), 0);
+		assert_eq!(Balances::free_balance(first), 100000);
+		assert_eq!(VanePayment::get_allowed_signers(1), None);
+
+		// The first payment is still open and can be reverted
+		assert_ok!(VanePayment::revert_fund(
+			Origin::signed(1),
+			0,
+			RevertReasons::WrongPayeeAddress
+		));
+		assert_eq!(Balances::free_balance(1), 970000);
+		assert_eq!(Balances::free_balance(first), 0);
+	})
+}
+
 // Checking Handling reverting for a payer.
+#[test]
+fn revert_fund_wrong_payee_address() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_eq!(Balances::free_balance(1), 900000);
+
+		// Only the payer can revert for a wrong address
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(2), 0, RevertReasons::WrongPayeeAddress),
+			Error::<Test>::NotAllowedToRevert
+		);
+
+		assert_ok!(VanePayment::revert_fund(
+			Origin::signed(1),
+			0,
+			RevertReasons::WrongPayeeAddress
+		));
+
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(Balances::free_balance(multi_id), 0);
+		assert_eq!(VanePayment::get_allowed_signers(0), None);
+		// Not a fault
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 0);
+	})
+}
+
+#[test]
+fn revert_fund_wrong_address_after_payee_confirmed_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(1), 0, RevertReasons::WrongPayeeAddress),
+			Error::<Test>::PayeeAlreadyConfirmed
+		);
+	})
+}
+
+#[test]
+fn revert_fund_wrong_address_ignores_other_payments_confirmations() {
+	new_test_ext().execute_with(|| {
+		for amount in [100000, 50000] {
+			assert_ok!(VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				amount,
+				PaymentAsset::Native,
+				ResolverChoice::None
+			));
+		}
+		let second = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(1)));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 1));
+
+		// The payee confirmed the second payment only, the first one can still be reverted
+		assert_ok!(VanePayment::revert_fund(
+			Origin::signed(1),
+			0,
+			RevertReasons::WrongPayeeAddress
+		));
+		assert_eq!(Balances::free_balance(1), 950000);
+		assert_eq!(Balances::free_balance(second), 50000);
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(1), 1, RevertReasons::WrongPayeeAddress),
+			Error::<Test>::PayeeAlreadyConfirmed
+		);
+	})
+}
+
+#[test]
+fn revert_fund_change_of_decision() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		// Payee cannot agree before the payer asks
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(2), 0, RevertReasons::ChangeOfDecision),
+			Error::<Test>::WaitForPayerToRequestRevert
+		);
+
+		assert_ok!(VanePayment::revert_fund(Origin::signed(1), 0, RevertReasons::ChangeOfDecision));
+		// Nothing is refunded until the payee agrees
+		assert_eq!(Balances::free_balance(1), 900000);
+
+		assert_ok!(VanePayment::revert_fund(Origin::signed(2), 0, RevertReasons::ChangeOfDecision));
+
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 1);
+		assert_eq!(VanePayment::get_revert_requests(1), None);
+	})
+}
+
+// Checking escrow expiry when the payee never confirms
+#[test]
+fn escrow_expiry_refunds_payer() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_eq!(VanePayment::get_payment_expiry(0), Some(11));
+
+		run_to_block(10);
+		assert_eq!(Balances::free_balance(1), 900000);
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(Balances::free_balance(multi_id), 0);
+		assert_eq!(VanePayment::get_allowed_signers(0), None);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 1);
+		System::assert_last_event(
+			crate::Event::EscrowExpired {
+				payment_id: 0,
+				multi_id,
+				payer: 1,
+				payee: 2,
+				timestamp: 11,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn failed_expiry_is_rolled_back_and_retried() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		// The multi_id cannot pay the refund
+		Balances::make_free_balance_be(&multi_id, 50000);
+		run_to_block(11);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Created));
+		assert_eq!(VanePayment::get_locked_amount(0), Some(100000));
+		assert_eq!(VanePayment::get_payment_expiry(0), Some(12));
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			Event::VanePayment(crate::Event::ExpiryFailed {
+				payment_id: 0,
+				retry_at: Some(12),
+				timestamp: 11,
+				..
+			})
+		)));
+
+		Balances::make_free_balance_be(&multi_id, 100000);
+		run_to_block(12);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Expired));
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+	})
+}
+
+// The payee confirmed in time, the payer never did
+#[test]
+fn escrow_expiry_faults_payer_after_payee_confirmed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Expired));
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 1);
+	})
+}
+
+#[test]
+fn settled_escrow_does_not_expire() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 900000);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+	})
+}
 
 // Handling reverting for a Multi-SIg call involving a seller
+#[test]
+fn revert_fund_payee_misbehaviour() {
+	new_test_ext().execute_with(|| {
+		// Payment with legal team account 3 as a resolver
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::zero()));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+
+		// The payee cannot sign for its own misbehaviour
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(2), 0, RevertReasons::PayeeMisbehaviour),
+			Error::<Test>::NotAllowedToRevert
+		);
+		// The legal team only rules on an open dispute
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(3), 0, RevertReasons::PayeeMisbehaviour),
+			Error::<Test>::NoDisputeRaised
+		);
+
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(1),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_ok!(VanePayment::revert_fund(
+			Origin::signed(3),
+			0,
+			RevertReasons::PayeeMisbehaviour
+		));
+
+		// Nothing moves until the ruling is executed
+		assert_eq!(Balances::free_balance(1), 898000);
+		assert_eq!(
+			VanePayment::get_ruling(0).map(|ruling| ruling.verdict),
+			Some(DisputeVerdict::RefundPayer)
+		);
+
+		run_to_block(4);
+		assert_ok!(VanePayment::execute_ruling(Origin::signed(5), 0));
+
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 1);
+	})
+}
 
 // Checking dispute handling by a legal team
+#[test]
+fn vane_pay_with_legal_team() {
+	new_test_ext().execute_with(|| {
+		// No legal team bonded yet
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				100000,
+				PaymentAsset::Native,
+				ResolverChoice::LegalTeam
+			),
+			Error::<Test>::NoResolverAvailable
+		);
+
+		assert_noop!(
+			VanePayment::add_resolver(Origin::signed(1), 3),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				100000,
+				PaymentAsset::Native,
+				ResolverChoice::LegalTeam
+			),
+			Error::<Test>::NoResolverAvailable
+		);
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::zero()));
+
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+
+		let accounts =
+			AccountSigners::<Test>::new(2, 1, Some(Resolver::LegalTeam(3))).with_nonce(Some(0));
+		let multi_id = VanePayment::derive_multi_id(accounts.clone());
+		assert_ne!(multi_id, VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0))));
+		assert_eq!(VanePayment::get_allowed_signers(0), Some(accounts));
+		assert_eq!(Balances::free_balance(multi_id), 100000);
+
+		// Both parties can still settle without the resolver
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+		assert_eq!(Balances::free_balance(2), 200000);
+	})
+}
+
+#[test]
+fn parties_are_never_their_own_legal_team() {
+	new_test_ext().execute_with(|| {
+		// The payee is the only bonded legal team
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 2));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(2), Perbill::zero()));
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				100000,
+				PaymentAsset::Native,
+				ResolverChoice::LegalTeam
+			),
+			Error::<Test>::NoResolverAvailable
+		);
+
+		// The rotation skips the payee and picks the next legal team
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::zero()));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+
+		let accounts =
+			AccountSigners::<Test>::new(2, 1, Some(Resolver::LegalTeam(3))).with_nonce(Some(0));
+		assert_eq!(VanePayment::get_allowed_signers(0), Some(accounts));
+	})
+}
+
+#[test]
+fn legal_team_releases_fund_to_payee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::zero()));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+
+		// Only the legal team can release, and only on an open dispute
+		assert_noop!(
+			VanePayment::release_fund(Origin::signed(2), 0),
+			Error::<Test>::NotAllowedToRelease
+		);
+		assert_noop!(
+			VanePayment::release_fund(Origin::signed(3), 0),
+			Error::<Test>::NoDisputeRaised
+		);
+
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(2),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_ok!(VanePayment::release_fund(Origin::signed(3), 0));
+
+		// The payer can appeal the release before it is executed
+		assert_eq!(Balances::free_balance(2), 98000);
+		assert_noop!(
+			VanePayment::execute_ruling(Origin::signed(5), 0),
+			Error::<Test>::AppealPeriodNotOver
+		);
+
+		run_to_block(4);
+		assert_ok!(VanePayment::execute_ruling(Origin::signed(5), 0));
+
+		assert_eq!(Balances::free_balance(2), 200000);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 1);
+		assert_eq!(VanePayment::get_allowed_signers(0), None);
+	})
+}
 
 // Checking handling dispute by governance
+#[test]
+fn governance_dispute_refunds_payer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::Governance
+		));
+
+		// Cannot resolve before a dispute is raised
+		assert_noop!(
+			VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::RefundPayer),
+			Error::<Test>::NoDisputeRaised
+		);
+		// Outsiders cannot raise a dispute
+		assert_noop!(
+			VanePayment::open_dispute(
+				Origin::signed(3),
+				0,
+				Default::default(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::NotAllowedToDispute
+		);
+
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(2),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		// Only governance decides a governance dispute
+		assert_noop!(
+			VanePayment::resolve_dispute(Origin::signed(3), 0, DisputeVerdict::RefundPayer),
+			Error::<Test>::NotAllowedToResolve
+		);
+		assert_noop!(
+			VanePayment::resolve_dispute(Origin::none(), 0, DisputeVerdict::RefundPayer),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::RefundPayer));
+
+		// The payee lost the dispute it opened, its deposit goes to the payer
+		assert_eq!(Balances::free_balance(1), 1_002_000);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 1);
+		assert_eq!(VanePayment::get_dispute(0), None);
+	})
+}
+
+#[test]
+fn governance_dispute_splits_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::Governance
+		));
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(1),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_ok!(VanePayment::resolve_dispute(
+			Origin::root(),
+			0,
+			DisputeVerdict::Split(sp_runtime::Perbill::from_percent(40))
+		));
+
+		// Nobody is at fault for a split and the deposit is returned
+		assert_eq!(Balances::free_balance(1), 960000);
+		assert_eq!(Balances::free_balance(2), 140000);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 0);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+	})
+}
+
+#[test]
+fn dispute_is_opened_with_deposit_and_evidence() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::zero()));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		// Nobody could decide a dispute on a payment without a resolver
+		assert_noop!(
+			VanePayment::open_dispute(Origin::signed(1), 1, Default::default(), H256::zero()),
+			Error::<Test>::NoDisputeResolver
+		);
+
+		let reason: BoundedVec<u8, ConstU32<8>> = b"late".to_vec().try_into().unwrap();
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(1),
+			0,
+			reason.clone(),
+			H256::repeat_byte(1)
+		));
+		assert_eq!(Balances::reserved_balance(1), 2000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Disputed));
+		assert_noop!(
+			VanePayment::open_dispute(Origin::signed(2), 0, Default::default(), H256::zero()),
+			Error::<Test>::DisputeAlreadyRaised
+		);
+
+		// Both sides attach evidence, up to MaxEvidence
+		assert_noop!(
+			VanePayment::add_evidence(Origin::signed(4), 0, H256::repeat_byte(2)),
+			Error::<Test>::NotAllowedToDispute
+		);
+		assert_ok!(VanePayment::add_evidence(Origin::signed(2), 0, H256::repeat_byte(2)));
+		assert_ok!(VanePayment::add_evidence(Origin::signed(1), 0, H256::repeat_byte(3)));
+		assert_noop!(
+			VanePayment::add_evidence(Origin::signed(2), 0, H256::repeat_byte(4)),
+			Error::<Test>::TooMuchEvidence
+		);
+		let dispute = VanePayment::get_dispute(0).unwrap();
+		assert_eq!(dispute.opened_by, 1);
+		assert_eq!(dispute.reason, reason);
+		assert_eq!(
+			dispute.evidence.into_inner(),
+			vec![(1, H256::repeat_byte(1)), (2, H256::repeat_byte(2)), (1, H256::repeat_byte(3))]
+		);
+
+		// The legal team of the payment decides, its ruling is executed once nobody appealed it
+		assert_noop!(
+			VanePayment::resolve_dispute(Origin::signed(4), 0, DisputeVerdict::ReleaseToPayee),
+			Error::<Test>::NotAllowedToResolve
+		);
+		assert_ok!(VanePayment::resolve_dispute(
+			Origin::signed(3),
+			0,
+			DisputeVerdict::ReleaseToPayee
+		));
+		run_to_block(4);
+		assert_ok!(VanePayment::execute_ruling(Origin::signed(5), 0));
+
+		// The payer lost the dispute it opened, its deposit goes to the payee
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 798000);
+		assert_eq!(Balances::free_balance(2), 202000);
+		assert_eq!(VanePayment::get_dispute(0), None);
+		System::assert_has_event(
+			crate::Event::DisputeDepositForfeited {
+				payment_id: 0,
+				from_account: 1,
+				to_account: 2,
+				amount: 2000,
+				timestamp: 4,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn resolver_registry_admits_and_bonds_legal_teams() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			VanePayment::bond_resolver(Origin::signed(3), Perbill::zero()),
+			Error::<Test>::NotAdmittedResolver
+		);
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_noop!(
+			VanePayment::add_resolver(Origin::root(), 3),
+			Error::<Test>::ResolverAlreadyExists
+		);
+		assert_noop!(
+			VanePayment::bond_resolver(Origin::signed(3), Perbill::from_percent(20)),
+			Error::<Test>::ResolverFeeTooHigh
+		);
+
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::from_percent(5)));
+		assert_eq!(Balances::reserved_balance(3), 5000);
+		assert_eq!(VanePayment::get_resolver().into_inner(), vec![3]);
+
+		// Bonding again only changes the fee
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::from_percent(10)));
+		assert_eq!(Balances::reserved_balance(3), 5000);
+		assert_eq!(VanePayment::get_resolver().into_inner(), vec![3]);
+		assert_eq!(
+			VanePayment::get_resolver_info(3),
+			Some(ResolverInfo { bond: 5000, fee: Perbill::from_percent(10) })
+		);
+
+		assert_ok!(VanePayment::remove_resolver(Origin::root(), 3));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert!(VanePayment::get_resolver().is_empty());
+		assert_eq!(VanePayment::get_resolver_info(3), None);
+	})
+}
+
+#[test]
+fn legal_team_ruling_is_executed_and_paid() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::from_percent(10)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+
+		// The fee is fixed when the payment is made
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::from_percent(5)));
+		assert_eq!(VanePayment::get_resolver_fee(0), Perbill::from_percent(10));
+
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(2),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_noop!(VanePayment::appeal_ruling(Origin::signed(1), 0), Error::<Test>::NoRuling);
+
+		assert_ok!(VanePayment::resolve_dispute(
+			Origin::signed(3),
+			0,
+			DisputeVerdict::ReleaseToPayee
+		));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Disputed));
+		assert_eq!(VanePayment::get_dispute_resolver(0), None);
+		assert_noop!(
+			VanePayment::execute_ruling(Origin::signed(5), 0),
+			Error::<Test>::AppealPeriodNotOver
+		);
+
+		run_to_block(4);
+		assert_noop!(
+			VanePayment::appeal_ruling(Origin::signed(1), 0),
+			Error::<Test>::AppealPeriodOver
+		);
+		assert_ok!(VanePayment::execute_ruling(Origin::signed(5), 0));
+
+		// The legal team takes its fee from the escrow, the payee gets the rest
+		assert_eq!(Balances::free_balance(3), 75000);
+		assert_eq!(Balances::free_balance(2), 190000);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(1), 900000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+		assert_eq!(VanePayment::get_ruling(0), None);
+	})
+}
+
+#[test]
+fn overturned_ruling_slashes_legal_team() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::from_percent(10)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(1),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_ok!(VanePayment::resolve_dispute(Origin::signed(3), 0, DisputeVerdict::RefundPayer));
+
+		// Governance only steps in on appeal
+		assert_noop!(
+			VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::ReleaseToPayee),
+			Error::<Test>::RulingNotAppealed
+		);
+		assert_noop!(
+			VanePayment::appeal_ruling(Origin::signed(4), 0),
+			Error::<Test>::NotAllowedToDispute
+		);
+		assert_ok!(VanePayment::appeal_ruling(Origin::signed(2), 0));
+		assert_noop!(
+			VanePayment::appeal_ruling(Origin::signed(2), 0),
+			Error::<Test>::AlreadyAppealed
+		);
+		// An appealed ruling waits for governance
+		assert_noop!(
+			VanePayment::execute_ruling(Origin::signed(5), 0),
+			Error::<Test>::AlreadyAppealed
+		);
+
+		assert_ok!(VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::ReleaseToPayee));
+
+		// Half of the bond goes to the treasury and the legal team leaves the rotation, it is
+		// not paid
+		assert_eq!(Balances::reserved_balance(3), 2500);
+		assert_eq!(Balances::free_balance(3), 65000);
+		assert_eq!(Balances::free_balance(TREASURY), 2500);
+		assert!(VanePayment::get_resolver().is_empty());
+		// The payer lost the dispute it opened
+		assert_eq!(Balances::free_balance(2), 202000);
+
+		// Topping the bond up brings it back
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::from_percent(10)));
+		assert_eq!(Balances::reserved_balance(3), 5000);
+		assert_eq!(VanePayment::get_resolver().into_inner(), vec![3]);
+	})
+}
+
+#[test]
+fn late_legal_team_is_replaced() {
+	new_test_ext().execute_with(|| {
+		for resolver in [3, 4] {
+			assert_ok!(VanePayment::add_resolver(Origin::root(), resolver));
+			assert_ok!(VanePayment::bond_resolver(Origin::signed(resolver), Perbill::zero()));
+		}
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::LegalTeam
+		));
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(1),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_eq!(VanePayment::get_dispute_resolver(0), Some((3, 6)));
+
+		// Governance does not pre-empt the legal team within its RulingPeriod
+		assert_noop!(
+			VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::RefundPayer),
+			Error::<Test>::LegalTeamStillRuling
+		);
+
+		run_to_block(6);
+		assert_eq!(VanePayment::get_dispute_resolver(0), Some((4, 11)));
+		System::assert_has_event(
+			crate::Event::ResolverReplaced {
+				payment_id: 0,
+				replaced: 3,
+				resolver: Some(4),
+				deadline: Some(11),
+				timestamp: 6,
+			}
+			.into(),
+		);
+		assert_noop!(
+			VanePayment::resolve_dispute(Origin::signed(3), 0, DisputeVerdict::RefundPayer),
+			Error::<Test>::NotAllowedToResolve
+		);
+
+		// Without another legal team, governance decides
+		assert_ok!(VanePayment::remove_resolver(Origin::root(), 3));
+		run_to_block(11);
+		assert_eq!(VanePayment::get_dispute_resolver(0), None);
+		assert_ok!(VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::RefundPayer));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Refunded));
+	})
+}
+
+#[test]
+fn legal_team_with_outstanding_disputes_keeps_its_bond() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 3));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(3), Perbill::zero()));
+		assert_ok!(VanePayment::add_resolver(Origin::root(), 4));
+		assert_ok!(VanePayment::bond_resolver(Origin::signed(4), Perbill::zero()));
+		for _ in 0..2 {
+			assert_ok!(VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				10000,
+				PaymentAsset::Native,
+				ResolverChoice::LegalTeam
+			));
+		}
+
+		// Nothing is assigned to legal team 4 yet, a dispute on its payment goes to 3
+		assert_ok!(VanePayment::remove_resolver(Origin::root(), 4));
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(1),
+			1,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_eq!(VanePayment::get_dispute_resolver(1), Some((3, 6)));
+
+		assert_noop!(
+			VanePayment::remove_resolver(Origin::root(), 3),
+			Error::<Test>::ResolverHasAssignments
+		);
+		assert_ok!(VanePayment::resolve_dispute(Origin::signed(3), 1, DisputeVerdict::RefundPayer));
+		assert_noop!(
+			VanePayment::remove_resolver(Origin::root(), 3),
+			Error::<Test>::ResolverHasAssignments
+		);
+
+		run_to_block(4);
+		assert_ok!(VanePayment::execute_ruling(Origin::signed(5), 1));
+		assert_eq!(VanePayment::get_resolver_assignments(3), 0);
+		assert_ok!(VanePayment::remove_resolver(Origin::root(), 3));
+		assert_eq!(Balances::reserved_balance(3), 0);
+	})
+}
+
+// Opens a payment from 1 to 2 and reverts it with ChangeOfDecision, recording a payer fault
+fn payer_changes_decision(payment_id: PaymentId) {
+	assert_ok!(VanePayment::vane_pay(
+		Origin::signed(1),
+		Some(2),
+		None,
+		100000,
+		PaymentAsset::Native,
+		ResolverChoice::None
+	));
+	assert_ok!(VanePayment::revert_fund(
+		Origin::signed(1),
+		payment_id,
+		RevertReasons::ChangeOfDecision
+	));
+	assert_ok!(VanePayment::revert_fund(
+		Origin::signed(2),
+		payment_id,
+		RevertReasons::ChangeOfDecision
+	));
+}
+
+#[test]
+fn repeated_faults_raise_deposit_and_bar_account() {
+	new_test_ext().execute_with(|| {
+		payer_changes_decision(0);
+		payer_changes_decision(1);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 2);
+		System::assert_has_event(
+			crate::Event::DepositRaised { account_id: 1, deposit: 1000, faults: 2, timestamp: 1 }
+				.into(),
+		);
+
+		// The next payment needs a deposit, returned once the payment is closed
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_eq!(Balances::reserved_balance(1), 1000);
+		assert_eq!(VanePayment::get_payment_deposit(2), Some((1, 1000)));
+		assert_ok!(VanePayment::revert_fund(Origin::signed(1), 2, RevertReasons::ChangeOfDecision));
+		assert_ok!(VanePayment::revert_fund(Origin::signed(2), 2, RevertReasons::ChangeOfDecision));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+
+		// Third fault bars the payer for BarDuration blocks
+		System::assert_has_event(
+			crate::Event::AccountBarred { account_id: 1, until: 6, faults: 3, timestamp: 1 }.into(),
+		);
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				100000,
+				PaymentAsset::Native,
+				ResolverChoice::None
+			),
+			Error::<Test>::AccountBarred
+		);
+		// The payee is not affected
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(2),
+			Some(1),
+			None,
+			10000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(1), Confirm::Payee, 3),
+			Error::<Test>::AccountBarred
+		);
+
+		run_to_block(6);
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payee, 3));
+		assert_eq!(VanePayment::get_barred_until(1), None);
+	})
+}
+
+#[test]
+fn faults_past_slash_threshold_slash_penalty() {
+	new_test_ext().execute_with(|| {
+		payer_changes_decision(0);
+		payer_changes_decision(1);
+		payer_changes_decision(2);
+
+		run_to_block(6);
+		payer_changes_decision(3);
+
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 4);
+		assert_eq!(Balances::free_balance(1), 998_000);
+		assert_eq!(Balances::free_balance(TREASURY), 2000);
+		System::assert_has_event(
+			crate::Event::FaultPenaltySlashed {
+				account_id: 1,
+				amount: 2000,
+				faults: 4,
+				timestamp: 6,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn fault_penalty_spares_reserved_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::reserve(&5, 400));
+		RevertedTxnPayer::<Test>::insert(5, 3);
+
+		VanePayment::record_fault(&5, FaultRole::Payer);
+
+		// Only the free balance above the existential deposit is taken
+		assert_eq!(Balances::free_balance(5), 500);
+		assert_eq!(Balances::reserved_balance(5), 400);
+		assert_eq!(Balances::free_balance(TREASURY), 100);
+		System::assert_has_event(
+			crate::Event::FaultPenaltySlashed {
+				account_id: 5,
+				amount: 100,
+				faults: 4,
+				timestamp: 1,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn faults_decay_after_window() {
+	new_test_ext().execute_with(|| {
+		payer_changes_decision(0);
+		payer_changes_decision(1);
+		assert_eq!(VanePayment::required_deposit(&1), 1000);
+
+		run_to_block(20);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 2);
+
+		run_to_block(21);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 0);
+		assert_eq!(VanePayment::required_deposit(&1), 0);
+		System::assert_last_event(
+			crate::Event::FaultExpired {
+				account_id: 1,
+				role: FaultRole::Payer,
+				faults: 0,
+				timestamp: 21,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn full_fault_decay_slot_does_not_block_refund() {
+	new_test_ext().execute_with(|| {
+		let full = |block: u64| {
+			let faults = vec![(10, FaultRole::Payee); 50];
+			FaultDecay::<Test>::insert(block, BoundedVec::try_from(faults).unwrap());
+		};
+
+		// The fault spills into the next block's decay slot
+		full(21);
+		payer_changes_decision(0);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 1);
+		assert_eq!(VanePayment::get_fault_decay(22).to_vec(), vec![(1, FaultRole::Payer)]);
+
+		run_to_block(21);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 1);
+		run_to_block(22);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 0);
+
+		// With both slots full the refund still goes through, the fault is kept
+		full(42);
+		full(43);
+		payer_changes_decision(1);
+		assert_eq!(VanePayment::get_payment_status(1), Some(PaymentStatus::Refunded));
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 1);
+	})
+}
+
+#[test]
+fn payment_status_follows_settlement() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Created));
+		System::assert_has_event(
+			crate::Event::PaymentStatusChanged {
+				payment_id: 0,
+				previous: None,
+				status: PaymentStatus::Created,
+				timestamp: 1,
+			}
+			.into(),
+		);
+
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::PayeeConfirmed));
+
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+		System::assert_has_event(
+			crate::Event::PaymentStatusChanged {
+				payment_id: 0,
+				previous: Some(PaymentStatus::PayeeConfirmed),
+				status: PaymentStatus::Settled,
+				timestamp: 1,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn payment_status_refunded_and_expired() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(3),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		assert_ok!(VanePayment::revert_fund(
+			Origin::signed(1),
+			0,
+			RevertReasons::WrongPayeeAddress
+		));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Refunded));
+
+		run_to_block(11);
+		assert_eq!(VanePayment::get_payment_status(1), Some(PaymentStatus::Expired));
+	})
+}
+
+#[test]
+fn disputed_payment_is_frozen() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::Governance
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(2),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Disputed));
+
+		// The payer cannot settle or take the funds back around the dispute
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0),
+			Error::<Test>::PaymentDisputed
+		);
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(1), 0, RevertReasons::ChangeOfDecision),
+			Error::<Test>::PaymentDisputed
+		);
+
+		// Nor does it expire
+		run_to_block(11);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Disputed));
+
+		assert_ok!(VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::ReleaseToPayee));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+		assert_eq!(Balances::free_balance(2), 200000);
+	})
+}
+
+// Opens a payment from 1 to 2 and settles it
+fn settle_payment(payment_id: PaymentId) {
+	assert_ok!(VanePayment::vane_pay(
+		Origin::signed(1),
+		Some(2),
+		None,
+		10000,
+		PaymentAsset::Native,
+		ResolverChoice::None
+	));
+	assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, payment_id));
+	assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, payment_id));
+}
+
+#[test]
+fn history_is_pruned_past_retention() {
+	new_test_ext().execute_with(|| {
+		for payment_id in 0..4 {
+			settle_payment(payment_id);
+		}
+
+		// MaxHistoryPerAccount is 3 in the mock, the first settlement is pruned
+		assert_eq!(VanePayment::get_payer_history_bounds(1), HistoryBounds { first: 1, next: 4 });
+		assert_eq!(VanePayment::get_payer_history(1, 0), None);
+		assert!(VanePayment::get_payer_history(1, 3).is_some());
+		assert_eq!(VanePayment::get_payee_history_bounds(2), HistoryBounds { first: 1, next: 4 });
+
+		// Starting before the oldest kept record starts at the oldest kept record
+		assert_eq!(
+			VanePayment::payer_history_page(&1, 0, 10),
+			(
+				vec![
+					VanePayment::get_payer_history(1, 1).unwrap(),
+					VanePayment::get_payer_history(1, 2).unwrap(),
+					VanePayment::get_payer_history(1, 3).unwrap(),
+				],
+				None
+			)
+		);
+	})
+}
+
+#[test]
+fn history_is_listed_page_by_page() {
+	new_test_ext().execute_with(|| {
+		for payment_id in 0..3 {
+			settle_payment(payment_id);
+		}
+
+		let (first_page, next) = VanePayment::payee_history_page(&2, 0, 2);
+		assert_eq!(first_page.len(), 2);
+		assert_eq!(next, Some(2));
+
+		let (second_page, next) = VanePayment::payee_history_page(&2, 2, 2);
+		assert_eq!(second_page, vec![VanePayment::get_payee_history(2, 2).unwrap()]);
+		assert_eq!(next, None);
+
+		// Nothing received by the payer
+		assert_eq!(VanePayment::payee_history_page(&1, 0, 2), (vec![], None));
+	})
+}
+
+fn split(payees: Vec<(u64, u64)>) -> BoundedVec<(u64, u64), ConstU32<3>> {
+	BoundedVec::try_from(payees).unwrap()
+}
+
+#[test]
+fn split_payment_pays_each_payee_their_share() {
+	new_test_ext().execute_with(|| {
+		ProtocolFee::set(sp_runtime::Perbill::from_percent(1));
+		assert_ok!(VanePayment::vane_pay_split(
+			Origin::signed(1),
+			split(vec![(2, 60000), (3, 30000), (4, 10000)]),
+			ResolverChoice::None
+		));
+		assert_eq!(Balances::free_balance(1), 900000);
+
+		// Payees confirm in any order, the payer has to wait for all of them
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(3), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0),
+			Error::<Test>::WaitForPayeeToConfirm
+		);
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(3), Confirm::Payee, 0),
+			Error::<Test>::PayeeAlreadyConfirmed
+		);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Created));
+
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(4), Confirm::Payee, 0));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::PayeeConfirmed));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+		assert_eq!(Balances::free_balance(2), 100000 + 59400);
+		assert_eq!(Balances::free_balance(3), 70000 + 29700);
+		assert_eq!(Balances::free_balance(4), 120000 + 9900);
+		assert_eq!(Balances::free_balance(TREASURY), 1000);
+		assert_eq!(VanePayment::get_split_payees(0), None);
+		assert_eq!(VanePayment::payer_history_page(&1, 0, 10).0.len(), 3);
+		assert_eq!(VanePayment::payee_history_page(&3, 0, 10).0.len(), 1);
+		System::assert_has_event(
+			crate::Event::SplitShareReleased {
+				payment_id: 0,
+				to_account: 3,
+				amount: 29700,
+				timestamp: 1,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn split_payment_rejects_bad_payees() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			VanePayment::vane_pay_split(Origin::signed(1), split(vec![]), ResolverChoice::None),
+			Error::<Test>::NoPayees
+		);
+		assert_noop!(
+			VanePayment::vane_pay_split(
+				Origin::signed(1),
+				split(vec![(2, 10000), (2, 10000)]),
+				ResolverChoice::None
+			),
+			Error::<Test>::DuplicatePayee
+		);
+		assert_noop!(
+			VanePayment::vane_pay_split(
+				Origin::signed(1),
+				split(vec![(2, 10000), (3, 100)]),
+				ResolverChoice::None
+			),
+			Error::<Test>::AmountBelowMinimum
+		);
+		// Every share has to stay above the existential deposit once the fee is taken out
+		ProtocolFee::set(sp_runtime::Perbill::from_percent(1));
+		assert_noop!(
+			VanePayment::vane_pay_split(
+				Origin::signed(1),
+				split(vec![(2, 10000), (3, 500)]),
+				ResolverChoice::None
+			),
+			Error::<Test>::AmountBelowMinimum
+		);
+	})
+}
+
+#[test]
+fn split_payment_is_refunded_through_a_dispute_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay_split(
+			Origin::signed(1),
+			split(vec![(2, 10000), (3, 10000)]),
+			ResolverChoice::Governance
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+
+		// The lead payee cannot give up the share of the other payee
+		assert_noop!(
+			VanePayment::reject_payment(Origin::signed(2), 0, None),
+			Error::<Test>::SplitPaymentNotSupported
+		);
+		assert_noop!(
+			VanePayment::propose_refund(Origin::signed(1), 0, Perbill::from_percent(50)),
+			Error::<Test>::SplitPaymentNotSupported
+		);
+		assert_noop!(
+			VanePayment::accept_refund(Origin::signed(2), 0, Perbill::from_percent(50)),
+			Error::<Test>::SplitPaymentNotSupported
+		);
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(1), 0, RevertReasons::ChangeOfDecision),
+			Error::<Test>::SplitPaymentNotSupported
+		);
+
+		assert_ok!(VanePayment::open_dispute(
+			Origin::signed(1),
+			0,
+			Default::default(),
+			H256::repeat_byte(1)
+		));
+		assert_ok!(VanePayment::resolve_dispute(Origin::root(), 0, DisputeVerdict::RefundPayer));
+
+		// Only the payee who never confirmed is at fault
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+		assert_eq!(VanePayment::get_failed_txn_payee(3), 1);
+	})
+}
+
+#[test]
+fn expired_split_payment_faults_payees_who_did_not_confirm() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay_split(
+			Origin::signed(1),
+			split(vec![(2, 10000), (3, 10000)]),
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+		assert_eq!(VanePayment::get_failed_txn_payee(3), 1);
+	})
+}
+
+fn milestones(amounts: Vec<u64>) -> BoundedVec<(u64, sp_core::H256), ConstU32<4>> {
+	let schedule = amounts
+		.into_iter()
+		.enumerate()
+		.map(|(index, amount)| (amount, blake2_256(&(index as u32).encode()).into()))
+		.collect::<Vec<_>>();
+	BoundedVec::try_from(schedule).unwrap()
+}
+
+#[test]
+fn milestones_are_released_one_by_one() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay_milestones(
+			Origin::signed(1),
+			2,
+			milestones(vec![30000, 70000]),
+			ResolverChoice::None
+		));
+		assert_eq!(Balances::free_balance(1), 900000);
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0),
+			Error::<Test>::ConfirmByMilestone
+		);
+
+		// Milestones can be confirmed in any order, each one needs both parties
+		assert_ok!(VanePayment::confirm_milestone(Origin::signed(2), 0, 1));
+		assert_noop!(
+			VanePayment::confirm_milestone(Origin::signed(2), 0, 1),
+			Error::<Test>::MilestoneAlreadyConfirmed
+		);
+		assert_ok!(VanePayment::confirm_milestone(Origin::signed(1), 0, 0));
+		assert_eq!(Balances::free_balance(2), 100000);
+
+		assert_ok!(VanePayment::confirm_milestone(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(2), 170000);
+		assert_eq!(VanePayment::get_locked_amount(0), Some(30000));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Created));
+		assert_noop!(
+			VanePayment::confirm_milestone(Origin::signed(1), 0, 1),
+			Error::<Test>::MilestoneAlreadyReleased
+		);
+		assert_noop!(
+			VanePayment::confirm_milestone(Origin::signed(1), 0, 2),
+			Error::<Test>::MilestoneNotFound
+		);
+
+		assert_ok!(VanePayment::confirm_milestone(Origin::signed(2), 0, 0));
+		assert_eq!(Balances::free_balance(2), 200000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+		assert_eq!(VanePayment::get_milestones(0), None);
+		assert_eq!(VanePayment::payee_history_page(&2, 0, 10).0.len(), 2);
+		System::assert_has_event(
+			crate::Event::MilestoneReleased {
+				payment_id: 0,
+				index: 0,
+				to_account: 2,
+				amount: 30000,
+				timestamp: 1,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn only_unreleased_milestones_are_refunded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay_milestones(
+			Origin::signed(1),
+			2,
+			milestones(vec![30000, 70000]),
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_milestone(Origin::signed(2), 0, 0));
+		assert_ok!(VanePayment::confirm_milestone(Origin::signed(1), 0, 0));
+
+		// The payee confirmed a milestone, so the address was right
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(1), 0, RevertReasons::WrongPayeeAddress),
+			Error::<Test>::PayeeAlreadyConfirmed
+		);
+
+		assert_ok!(VanePayment::revert_fund(Origin::signed(1), 0, RevertReasons::ChangeOfDecision));
+		assert_ok!(VanePayment::revert_fund(Origin::signed(2), 0, RevertReasons::ChangeOfDecision));
+		assert_eq!(Balances::free_balance(1), 970000);
+		assert_eq!(Balances::free_balance(2), 130000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Refunded));
+	})
+}
+
+#[test]
+fn milestone_schedule_is_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			VanePayment::vane_pay_milestones(
+				Origin::signed(1),
+				2,
+				milestones(vec![]),
+				ResolverChoice::None
+			),
+			Error::<Test>::NoMilestones
+		);
+		assert_noop!(
+			VanePayment::vane_pay_milestones(
+				Origin::signed(1),
+				2,
+				milestones(vec![30000, 100]),
+				ResolverChoice::None
+			),
+			Error::<Test>::AmountBelowMinimum
+		);
+
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			10000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_noop!(
+			VanePayment::confirm_milestone(Origin::signed(2), 0, 0),
+			Error::<Test>::NotMilestonePayment
+		);
+	})
+}
+
+// Creates asset 7, sufficient on its own, and asset 8 which is not, and mints both to account 1
+fn create_assets() {
+	assert_ok!(Assets::force_create(Origin::root(), 7, 1, true, 10));
+	assert_ok!(Assets::force_create(Origin::root(), 8, 1, false, 10));
+	assert_ok!(Assets::mint(Origin::signed(1), 7, 1, 200000));
+	assert_ok!(Assets::mint(Origin::signed(1), 8, 1, 200000));
+}
+
+#[test]
+fn asset_payment_is_settled_in_the_asset() {
+	new_test_ext().execute_with(|| {
+		create_assets();
+		ProtocolFee::set(sp_runtime::Perbill::from_percent(1));
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Asset(7),
+			ResolverChoice::None
+		));
+		assert_eq!(Assets::balance(7, 1), 100000);
+		assert_eq!(Assets::balance(7, multi_id), 100000);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_payment_asset(0), PaymentAsset::Asset(7));
+
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+		assert_eq!(Assets::balance(7, 2), 99000);
+		assert_eq!(Assets::balance(7, TREASURY), 1000);
+		assert_eq!(Assets::balance(7, multi_id), 0);
+		assert_eq!(Balances::free_balance(2), 100000);
+		assert_eq!(VanePayment::get_payment_asset(0), PaymentAsset::Native);
+	})
+}
+
+#[test]
+fn asset_payment_is_refunded_in_the_asset() {
+	new_test_ext().execute_with(|| {
+		create_assets();
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+
+		// Asset 8 is not sufficient, the open payment keeps the multi_id alive
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Asset(8),
+			ResolverChoice::None
+		));
+		assert_eq!(Assets::balance(8, multi_id), 100000);
+		assert!(System::account_exists(&multi_id));
+
+		assert_ok!(VanePayment::revert_fund(
+			Origin::signed(1),
+			0,
+			RevertReasons::WrongPayeeAddress
+		));
+		assert_eq!(Assets::balance(8, 1), 200000);
+		assert_eq!(Assets::balance(8, multi_id), 0);
+		assert!(!System::account_exists(&multi_id));
+	})
+}
+
+#[test]
+fn asset_fee_is_not_charged_when_it_cannot_be_credited() {
+	new_test_ext().execute_with(|| {
+		create_assets();
+		ProtocolFee::set(sp_runtime::Perbill::from_percent(1));
+
+		// The treasury holds no native balance, so it cannot receive asset 8
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Asset(8),
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+		assert_eq!(Assets::balance(8, 2), 100000);
+		assert_eq!(Assets::balance(8, TREASURY), 0);
+
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				5,
+				PaymentAsset::Asset(7),
+				ResolverChoice::None
+			),
+			Error::<Test>::AmountBelowMinimum
+		);
+	})
+}
+
+#[test]
+fn payer_cancels_before_payee_confirms() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_noop!(
+			VanePayment::cancel_payment(Origin::signed(2), 0),
+			Error::<Test>::NotAllowedToCancel
+		);
+
+		assert_ok!(VanePayment::cancel_payment(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_allowed_signers(0), None);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Cancelled));
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 0);
+		System::assert_last_event(
+			crate::Event::PaymentCancelled {
+				payment_id: 0,
+				multi_id,
+				payer: 1,
+				amount: 100000,
+				timestamp: 1,
+			}
+			.into(),
+		);
+		assert_noop!(
+			VanePayment::cancel_payment(Origin::signed(1), 0),
+			Error::<Test>::PaymentNotFound
+		);
+	})
+}
+
+#[test]
+fn cancel_after_payee_confirmed_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+
+		assert_noop!(
+			VanePayment::cancel_payment(Origin::signed(1), 0),
+			Error::<Test>::UseRevertFund
+		);
+	})
+}
+
+#[test]
+fn payee_rejects_payment_with_reason() {
+	new_test_ext().execute_with(|| {
+		let multi_id = VanePayment::derive_multi_id(new_acc(2, 1).with_nonce(Some(0)));
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_noop!(
+			VanePayment::reject_payment(Origin::signed(1), 0, None),
+			Error::<Test>::NotAllowedToReject
+		);
+
+		let reason: BoundedVec<u8, ConstU32<8>> =
+			BoundedVec::try_from(b"NOSTOCK".to_vec()).unwrap();
+		assert_ok!(VanePayment::reject_payment(Origin::signed(2), 0, Some(reason.clone())));
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Refunded));
+		assert_eq!(VanePayment::get_rejection_reason(0), Some(reason.clone()));
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 0);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+		System::assert_last_event(
+			crate::Event::PaymentRejected {
+				payment_id: 0,
+				multi_id,
+				payee: 2,
+				payer: 1,
+				amount: 100000,
+				reason: Some(reason),
+				timestamp: 1,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn payee_rejects_payment_without_reason() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::reject_payment(Origin::signed(2), 0, None));
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_rejection_reason(0), None);
+	})
+}
+
+#[test]
+fn closed_payment_status_is_pruned_after_retention() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				10000,
+				PaymentAsset::Native,
+				ResolverChoice::None
+			));
+		}
+		let reason: BoundedVec<u8, ConstU32<8>> =
+			BoundedVec::try_from(b"NOSTOCK".to_vec()).unwrap();
+		assert_ok!(VanePayment::reject_payment(Origin::signed(2), 0, Some(reason.clone())));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 1));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 1));
+		assert_ok!(VanePayment::cancel_payment(Origin::signed(1), 2));
+		// StatusRetention is 30 in the mock
+		for payment_id in 0..3 {
+			assert_eq!(VanePayment::get_closed_payment(31, payment_id), Some(()));
+		}
+		let kept = || (0..3).filter(|id| VanePayment::get_payment_status(id).is_some()).count();
+
+		run_to_block(30);
+		assert_eq!(kept(), 3);
+		assert_eq!(VanePayment::get_rejection_reason(0), Some(reason));
+
+		// MaxClosedPerBlock is 2 in the mock, the third payment waits for the next block
+		run_to_block(31);
+		assert_eq!(kept(), 1);
+		assert_eq!(VanePayment::get_next_pruned_block(), 31);
+
+		run_to_block(32);
+		assert_eq!(kept(), 0);
+		assert_eq!(VanePayment::get_rejection_reason(0), None);
+		assert_eq!(VanePayment::get_closed_payment(31, 2), None);
+		assert_eq!(VanePayment::get_next_pruned_block(), 32);
+	})
+}
+
+#[test]
+fn htlc_payment_is_claimed_with_preimage() {
+	new_test_ext().execute_with(|| {
+		let hashlock = blake2_256(b"delivery");
+		let preimage: BoundedVec<u8, ConstU32<32>> = b"delivery".to_vec().try_into().unwrap();
+		let wrong: BoundedVec<u8, ConstU32<32>> = b"guess".to_vec().try_into().unwrap();
+
+		assert_noop!(
+			VanePayment::vane_pay_htlc(
+				Origin::signed(1),
+				2,
+				100000,
+				PaymentAsset::Native,
+				hashlock,
+				0
+			),
+			Error::<Test>::InvalidTimelock
+		);
+		assert_noop!(
+			VanePayment::vane_pay_htlc(
+				Origin::signed(1),
+				2,
+				100000,
+				PaymentAsset::Native,
+				hashlock,
+				11
+			),
+			Error::<Test>::InvalidTimelock
+		);
+		assert_ok!(VanePayment::vane_pay_htlc(
+			Origin::signed(1),
+			2,
+			100000,
+			PaymentAsset::Native,
+			hashlock,
+			5
+		));
+		assert_eq!(Balances::free_balance(1), 900000);
+
+		// Neither the confirm flow nor the payer alone can move a hash locked payment
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0),
+			Error::<Test>::ClaimWithPreimage
+		);
+		assert_noop!(
+			VanePayment::cancel_payment(Origin::signed(1), 0),
+			Error::<Test>::TimelockNotExpired
+		);
+		assert_noop!(
+			VanePayment::revert_fund(Origin::signed(1), 0, RevertReasons::WrongPayeeAddress),
+			Error::<Test>::TimelockNotExpired
+		);
+		assert_noop!(
+			VanePayment::reclaim_htlc(Origin::signed(1), 0),
+			Error::<Test>::TimelockNotExpired
+		);
+
+		assert_noop!(
+			VanePayment::claim_htlc(Origin::signed(1), 0, preimage.clone()),
+			Error::<Test>::NotPaymentParticipant
+		);
+		assert_noop!(
+			VanePayment::claim_htlc(Origin::signed(2), 0, wrong),
+			Error::<Test>::InvalidPreimage
+		);
+
+		assert_ok!(VanePayment::claim_htlc(Origin::signed(2), 0, preimage.clone()));
+		assert_eq!(Balances::free_balance(2), 200000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+		assert_eq!(VanePayment::get_hash_lock(0), None);
+		System::assert_has_event(
+			crate::Event::HashLockClaimed { payment_id: 0, payee: 2, preimage, timestamp: 1 }
+				.into(),
+		);
+	})
+}
+
+#[test]
+fn htlc_payment_is_reclaimed_after_timelock() {
+	new_test_ext().execute_with(|| {
+		let preimage: BoundedVec<u8, ConstU32<32>> = b"delivery".to_vec().try_into().unwrap();
+		assert_ok!(VanePayment::vane_pay_htlc(
+			Origin::signed(1),
+			2,
+			100000,
+			PaymentAsset::Native,
+			blake2_256(b"delivery"),
+			5
+		));
+
+		run_to_block(6);
+		assert_noop!(
+			VanePayment::claim_htlc(Origin::signed(2), 0, preimage),
+			Error::<Test>::TimelockExpired
+		);
+		assert_noop!(
+			VanePayment::reclaim_htlc(Origin::signed(2), 0),
+			Error::<Test>::NotPaymentParticipant
+		);
+
+		assert_ok!(VanePayment::reclaim_htlc(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Expired));
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+		System::assert_last_event(
+			crate::Event::HashLockReclaimed {
+				payment_id: 0,
+				payer: 1,
+				amount: 100000,
+				timestamp: 6,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn unclaimed_htlc_payment_expires_without_fault() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay_htlc(
+			Origin::signed(1),
+			2,
+			100000,
+			PaymentAsset::Native,
+			blake2_256(b"delivery"),
+			10
+		));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Expired));
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+	})
+}
+
+#[test]
+fn claimable_payment_is_claimed_with_secret() {
+	new_test_ext().execute_with(|| {
+		let secret = [3u8; 32];
+		let commitment = (secret, 4u64).using_encoded(blake2_256);
+
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				None,
+				None,
+				100000,
+				PaymentAsset::Native,
+				ResolverChoice::None
+			),
+			Error::<Test>::PayeeOrCommitment
+		);
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				Some(commitment),
+				100000,
+				PaymentAsset::Native,
+				ResolverChoice::None
+			),
+			Error::<Test>::PayeeOrCommitment
+		);
+		assert_noop!(
+			VanePayment::vane_pay(
+				Origin::signed(1),
+				None,
+				Some(commitment),
+				100000,
+				PaymentAsset::Native,
+				ResolverChoice::Governance
+			),
+			Error::<Test>::ResolverNotAllowed
+		);
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			None,
+			Some(commitment),
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_eq!(Balances::free_balance(1), 900000);
+		assert_eq!(VanePayment::get_claim_commitment(0), Some(commitment));
+
+		assert_noop!(
+			VanePayment::cancel_payment(Origin::signed(1), 0),
+			Error::<Test>::ClaimableUntilExpiry
+		);
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0),
+			Error::<Test>::ClaimWithSecret
+		);
+		assert_noop!(
+			VanePayment::claim_payment(Origin::signed(4), 0, [4u8; 32]),
+			Error::<Test>::InvalidSecret
+		);
+		// The secret alone is not enough, it is bound to account 4
+		assert_noop!(
+			VanePayment::claim_payment(Origin::signed(3), 0, secret),
+			Error::<Test>::InvalidSecret
+		);
+
+		assert_ok!(VanePayment::claim_payment(Origin::signed(4), 0, secret));
+		assert_eq!(Balances::free_balance(4), 220000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+		assert_eq!(VanePayment::get_claim_commitment(0), None);
+		System::assert_last_event(
+			crate::Event::PaymentClaimed { payment_id: 0, payee: 4, timestamp: 1 }.into(),
+		);
+		assert_noop!(
+			VanePayment::claim_payment(Origin::signed(4), 0, secret),
+			Error::<Test>::PaymentNotFound
+		);
+	})
+}
+
+#[test]
+fn barred_accounts_still_claim_and_reclaim() {
+	new_test_ext().execute_with(|| {
+		let preimage: BoundedVec<u8, ConstU32<32>> = b"delivery".to_vec().try_into().unwrap();
+		let secret = [3u8; 32];
+		for _ in 0..2 {
+			assert_ok!(VanePayment::vane_pay_htlc(
+				Origin::signed(1),
+				2,
+				100000,
+				PaymentAsset::Native,
+				blake2_256(b"delivery"),
+				5
+			));
+		}
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			None,
+			Some((secret, 4u64).using_encoded(blake2_256)),
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		for account in [1, 2, 4] {
+			BarredUntil::<Test>::insert(account, 50);
+		}
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0),
+			Error::<Test>::AccountBarred
+		);
+
+		// The funds are committed to the payee, being barred does not take them back
+		assert_ok!(VanePayment::claim_htlc(Origin::signed(2), 0, preimage));
+		assert_eq!(Balances::free_balance(2), 200000);
+		assert_ok!(VanePayment::claim_payment(Origin::signed(4), 2, secret));
+		assert_eq!(Balances::free_balance(4), 220000);
+
+		// Nor does it keep the payer from its own funds once the timelock has expired
+		run_to_block(6);
+		assert_ok!(VanePayment::reclaim_htlc(Origin::signed(1), 1));
+		assert_eq!(Balances::free_balance(1), 800000);
+		assert_eq!(VanePayment::get_payment_status(1), Some(PaymentStatus::Expired));
+	})
+}
+
+#[test]
+fn unclaimed_payment_is_refunded_after_expiry() {
+	new_test_ext().execute_with(|| {
+		let commitment = ([3u8; 32], 4u64).using_encoded(blake2_256);
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			None,
+			Some(commitment),
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(1), 1_000_000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Expired));
+		assert_noop!(
+			VanePayment::claim_payment(Origin::signed(4), 0, [3u8; 32]),
+			Error::<Test>::PaymentNotFound
+		);
+	})
+}
+
+#[test]
+fn partial_refund_is_negotiated_and_settled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+
+		assert_noop!(
+			VanePayment::accept_refund(Origin::signed(1), 0, Perbill::from_percent(50)),
+			Error::<Test>::NoRefundProposal
+		);
+		assert_noop!(
+			VanePayment::propose_refund(Origin::signed(3), 0, Perbill::from_percent(50)),
+			Error::<Test>::NotPaymentParticipant
+		);
+		assert_noop!(
+			VanePayment::propose_refund(Origin::signed(2), 0, Perbill::zero()),
+			Error::<Test>::InvalidRefundSplit
+		);
+		assert_noop!(
+			VanePayment::propose_refund(Origin::signed(2), 0, Perbill::one()),
+			Error::<Test>::InvalidRefundSplit
+		);
+
+		assert_ok!(VanePayment::propose_refund(Origin::signed(2), 0, Perbill::from_percent(60)));
+		assert_noop!(
+			VanePayment::accept_refund(Origin::signed(2), 0, Perbill::from_percent(60)),
+			Error::<Test>::CannotAcceptOwnProposal
+		);
+		// The payer counters, which replaces the payee's proposal
+		assert_ok!(VanePayment::propose_refund(Origin::signed(1), 0, Perbill::from_percent(50)));
+		assert_eq!(VanePayment::get_refund_proposal(0), Some((1, Perbill::from_percent(50))));
+		assert_noop!(
+			VanePayment::accept_refund(Origin::signed(2), 0, Perbill::from_percent(60)),
+			Error::<Test>::RefundProposalChanged
+		);
+
+		assert_ok!(VanePayment::accept_refund(Origin::signed(2), 0, Perbill::from_percent(50)));
+		assert_eq!(Balances::free_balance(1), 950000);
+		assert_eq!(Balances::free_balance(2), 150000);
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+		assert_eq!(VanePayment::get_refund_proposal(0), None);
+		assert_eq!(VanePayment::get_failed_txn_payer(1), 0);
+		assert_eq!(VanePayment::get_failed_txn_payee(2), 0);
+		assert_eq!(VanePayment::payee_history_page(&2, 0, 10).0.len(), 1);
+		System::assert_last_event(
+			crate::Event::PartialRefundSettled {
+				payment_id: 0,
+				payer: 1,
+				payee: 2,
+				refunded: 50000,
+				payee_share: Perbill::from_percent(50),
+				timestamp: 1,
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn confirm_pay_checks_the_role_of_the_caller() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(VanePayment::vane_pay(
+			Origin::signed(1),
+			Some(2),
+			None,
+			100000,
+			PaymentAsset::Native,
+			ResolverChoice::None
+		));
+
+		// The payer cannot take the payee's slot, nor the payee confirm as the payer
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(1), Confirm::Payee, 0),
+			Error::<Test>::NotPaymentParticipant
+		);
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payer, 0),
+			Error::<Test>::NotPaymentParticipant
+		);
+		assert!(VanePayment::get_signers(0).is_empty());
+
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+		assert_noop!(
+			VanePayment::confirm_pay(Origin::signed(2), Confirm::Payer, 0),
+			Error::<Test>::NotPaymentParticipant
+		);
+		assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+		assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+	})
+}
+
+// Payment lifecycle checked against every escrow backend, with the mock runtime of the calling
+// module in scope
+macro_rules! escrow_backend_tests {
+	() => {
+		use crate::helper::{
+			Confirm, DisputeVerdict, PaymentAsset, PaymentStatus, ResolverChoice, RevertReasons,
+		};
+		use frame_support::{assert_ok, traits::ReservableCurrency};
+		use sp_core::H256;
+
+		fn pay(resolver: ResolverChoice) {
+			assert_ok!(VanePayment::vane_pay(
+				Origin::signed(1),
+				Some(2),
+				None,
+				100000,
+				PaymentAsset::Native,
+				resolver
+			));
+		}
+
+		#[test]
+		fn vane_pay_locks_funds() {
+			new_test_ext().execute_with(|| {
+				pay(ResolverChoice::None);
+
+				assert_eq!(Balances::free_balance(1), 900000);
+				assert_eq!(VanePayment::get_locked_amount(0), Some(100000));
+				assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Created));
+			})
+		}
+
+		#[test]
+		fn confirm_pay_settles_to_payee() {
+			new_test_ext().execute_with(|| {
+				pay(ResolverChoice::None);
+				assert_ok!(VanePayment::confirm_pay(Origin::signed(2), Confirm::Payee, 0));
+				assert_ok!(VanePayment::confirm_pay(Origin::signed(1), Confirm::Payer, 0));
+
+				assert_eq!(Balances::free_balance(1), 900000);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(Balances::free_balance(2), 200000);
+				assert_eq!(VanePayment::get_locked_amount(0), None);
+				assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Settled));
+			})
+		}
+
+		#[test]
+		fn revert_fund_refunds_payer() {
+			new_test_ext().execute_with(|| {
+				pay(ResolverChoice::None);
+				assert_ok!(VanePayment::revert_fund(
+					Origin::signed(1),
+					0,
+					RevertReasons::WrongPayeeAddress
+				));
+				assert_eq!(Balances::free_balance(1), 1_000_000);
+				assert_eq!(Balances::reserved_balance(1), 0);
+
+				pay(ResolverChoice::None);
+				assert_ok!(VanePayment::revert_fund(
+					Origin::signed(1),
+					1,
+					RevertReasons::ChangeOfDecision
+				));
+				assert_ok!(VanePayment::revert_fund(
+					Origin::signed(2),
+					1,
+					RevertReasons::ChangeOfDecision
+				));
+				assert_eq!(Balances::free_balance(1), 1_000_000);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(Balances::free_balance(2), 100_000);
+				assert_eq!(VanePayment::get_failed_txn_payer(1), 1);
+			})
+		}
+
+		#[test]
+		fn expiry_refunds_payer() {
+			new_test_ext().execute_with(|| {
+				pay(ResolverChoice::None);
+
+				run_to_block(11);
+				assert_eq!(Balances::free_balance(1), 1_000_000);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Expired));
+				assert_eq!(VanePayment::get_failed_txn_payee(2), 1);
+			})
+		}
+
+		#[test]
+		fn dispute_is_settled_by_governance() {
+			new_test_ext().execute_with(|| {
+				pay(ResolverChoice::Governance);
+				pay(ResolverChoice::Governance);
+				for payment_id in [0, 1] {
+					assert_ok!(VanePayment::open_dispute(
+						Origin::signed(1),
+						payment_id,
+						Default::default(),
+						H256::repeat_byte(1)
+					));
+				}
+
+				assert_ok!(VanePayment::resolve_dispute(
+					Origin::root(),
+					0,
+					DisputeVerdict::RefundPayer
+				));
+				assert_eq!(Balances::free_balance(1), 898_000);
+				assert_ok!(VanePayment::resolve_dispute(
+					Origin::root(),
+					1,
+					DisputeVerdict::ReleaseToPayee
+				));
+
+				// The payer won the first dispute and lost the second one, with its deposit
+				assert_eq!(Balances::free_balance(1), 898_000);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(Balances::free_balance(2), 202000);
+				assert_eq!(VanePayment::get_payment_status(0), Some(PaymentStatus::Refunded));
+				assert_eq!(VanePayment::get_payment_status(1), Some(PaymentStatus::Settled));
+			})
+		}
+	};
+}
+
+mod multi_id_escrow {
+	use crate::mock::*;
+
+	escrow_backend_tests!();
+}
+
+mod reserve_escrow {
+	use crate::mock::reserve_escrow::*;
+
+	escrow_backend_tests!();
+}
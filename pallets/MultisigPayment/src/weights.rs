@@ -0,0 +1,706 @@
+//! Weights for pallet_multisig_payment
+//!
+//! PLACEHOLDER VALUES, NOT BENCHMARK OUTPUT. The benchmarks in benchmarking.rs have not been run
+//! on reference hardware yet. Every execution time below is a hand estimate and the `Storage`
+//! comments list what the code reads and writes in the benchmarked worst case. The file only
+//! follows the layout of the benchmark CLI so it can be replaced by its output, generate it with
+//! the command below before relying on these weights in production.
+
+// Command to generate it:
+// ./target/release/vane-node
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_multisig_payment
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=pallets/MultisigPayment/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_multisig_payment.
+pub trait WeightInfo {
+	fn vane_pay() -> Weight;
+	fn vane_pay_split(p: u32, ) -> Weight;
+	fn vane_pay_milestones(m: u32, ) -> Weight;
+	fn vane_pay_htlc() -> Weight;
+	fn claim_htlc() -> Weight;
+	fn reclaim_htlc() -> Weight;
+	fn claim_payment() -> Weight;
+	fn confirm_pay(p: u32, ) -> Weight;
+	fn confirm_milestone() -> Weight;
+	fn revert_fund() -> Weight;
+	fn cancel_payment() -> Weight;
+	fn reject_payment() -> Weight;
+	fn propose_refund() -> Weight;
+	fn accept_refund() -> Weight;
+	fn release_fund() -> Weight;
+	fn add_resolver() -> Weight;
+	fn bond_resolver() -> Weight;
+	fn remove_resolver() -> Weight;
+	fn open_dispute() -> Weight;
+	fn add_evidence() -> Weight;
+	fn resolve_dispute() -> Weight;
+	fn appeal_ruling() -> Weight;
+	fn execute_ruling() -> Weight;
+	fn refund_expired_payments(n: u32, ) -> Weight;
+	fn decay_faults(n: u32, ) -> Weight;
+	fn replace_late_resolvers(n: u32, ) -> Weight;
+	fn prune_closed_payments(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_multisig_payment using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment ResolverSigner (r:1 w:0)
+	// Storage: VanePayment NextResolver (r:1 w:1)
+	// Storage: VanePayment NextPaymentId (r:1 w:1)
+	// Storage: VanePayment AllowedSigners (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:0 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ExpiringPayments (r:1 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment RevertedTxnPayer (r:1 w:0)
+	// Storage: VanePayment RevertedTxnPayee (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	fn vane_pay() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment ResolverSigner (r:1 w:0)
+	// Storage: VanePayment NextResolver (r:1 w:1)
+	// Storage: VanePayment NextPaymentId (r:1 w:1)
+	// Storage: VanePayment AllowedSigners (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:0 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ExpiringPayments (r:1 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment RevertedTxnPayer (r:1 w:0)
+	// Storage: VanePayment RevertedTxnPayee (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: VanePayment SplitPayees (r:0 w:1)
+	fn vane_pay_split(p: u32, ) -> Weight {
+		(101_000_000 as Weight)
+			.saturating_add((310_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment ResolverSigner (r:1 w:0)
+	// Storage: VanePayment NextResolver (r:1 w:1)
+	// Storage: VanePayment NextPaymentId (r:1 w:1)
+	// Storage: VanePayment AllowedSigners (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:0 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ExpiringPayments (r:1 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment RevertedTxnPayer (r:1 w:0)
+	// Storage: VanePayment RevertedTxnPayee (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	fn vane_pay_milestones(m: u32, ) -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add((240_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment NextPaymentId (r:1 w:1)
+	// Storage: VanePayment AllowedSigners (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:0 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ExpiringPayments (r:1 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment RevertedTxnPayer (r:1 w:0)
+	// Storage: VanePayment RevertedTxnPayee (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: VanePayment HashLocks (r:0 w:1)
+	fn vane_pay_htlc() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment HashLocks (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:1 w:2)
+	// Storage: VanePayment PaymentAssets (r:1 w:1)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment PayerHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayerHistory (r:0 w:2)
+	// Storage: VanePayment PayeeHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayeeHistory (r:0 w:2)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment SplitConfirmations (r:0 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	fn claim_htlc() -> Weight {
+		(224_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(25 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment HashLocks (r:1 w:1)
+	// Storage: VanePayment LockedAmount (r:2 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment PaymentAssets (r:1 w:1)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment SplitPayees (r:0 w:1)
+	// Storage: VanePayment SplitConfirmations (r:0 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	fn reclaim_htlc() -> Weight {
+		(82_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment ClaimCommitments (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:1 w:2)
+	// Storage: VanePayment PaymentAssets (r:1 w:1)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment PayerHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayerHistory (r:0 w:2)
+	// Storage: VanePayment PayeeHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayeeHistory (r:0 w:2)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment SplitConfirmations (r:0 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	// Storage: VanePayment HashLocks (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	fn claim_payment() -> Weight {
+		(221_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(26 as Weight))
+	}
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment ConfirmedSigners (r:1 w:2)
+	// Storage: VanePayment LockedAmount (r:1 w:2)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment SplitConfirmations (r:1 w:1)
+	// Storage: VanePayment PayerHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayerHistory (r:0 w:2)
+	// Storage: VanePayment PayeeHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayeeHistory (r:0 w:2)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	// Storage: VanePayment Milestones (r:1 w:1)
+	fn confirm_pay(p: u32, ) -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add((38_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(22 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: VanePayment BarredUntil (r:1 w:0)
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment Milestones (r:1 w:2)
+	// Storage: VanePayment LockedAmount (r:1 w:2)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: VanePayment PayerHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayerHistory (r:0 w:2)
+	// Storage: VanePayment PayeeHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayeeHistory (r:0 w:2)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment SplitConfirmations (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	fn confirm_milestone() -> Weight {
+		(131_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(24 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment SplitPayees (r:1 w:0)
+	// Storage: VanePayment RevertRequests (r:1 w:1)
+	// Storage: VanePayment FaultDecay (r:2 w:1)
+	// Storage: VanePayment RevertedTxnPayer (r:2 w:1)
+	// Storage: VanePayment RevertedTxnPayee (r:2 w:0)
+	// Storage: VanePayment BarredUntil (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:1 w:1)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	fn revert_fund() -> Weight {
+		(121_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment ConfirmedSigners (r:1 w:1)
+	// Storage: VanePayment SplitConfirmations (r:1 w:1)
+	// Storage: VanePayment Milestones (r:1 w:1)
+	// Storage: VanePayment LockedAmount (r:2 w:1)
+	// Storage: VanePayment PaymentAssets (r:1 w:1)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment SplitPayees (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	fn cancel_payment() -> Weight {
+		(84_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment LockedAmount (r:2 w:1)
+	// Storage: VanePayment PaymentAssets (r:1 w:1)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment SplitConfirmations (r:0 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	// Storage: VanePayment RejectionReasons (r:0 w:1)
+	fn reject_payment() -> Weight {
+		(83_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:0)
+	// Storage: VanePayment PaymentStatuses (r:1 w:0)
+	// Storage: VanePayment SplitPayees (r:1 w:0)
+	// Storage: VanePayment RefundProposals (r:0 w:1)
+	fn propose_refund() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment RefundProposals (r:1 w:1)
+	// Storage: VanePayment LockedAmount (r:2 w:3)
+	// Storage: VanePayment PaymentAssets (r:2 w:1)
+	// Storage: VanePayment EscrowedTotal (r:2 w:2)
+	// Storage: System Account (r:4 w:4)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment PayerHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayerHistory (r:0 w:2)
+	// Storage: VanePayment PayeeHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayeeHistory (r:0 w:2)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment SplitConfirmations (r:0 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	// Storage: VanePayment HashLocks (r:0 w:1)
+	// Storage: VanePayment ClaimCommitments (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	fn accept_refund() -> Weight {
+		(243_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(30 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:0)
+	// Storage: VanePayment Disputes (r:1 w:0)
+	// Storage: VanePayment DisputeResolvers (r:1 w:1)
+	// Storage: VanePayment Rulings (r:0 w:1)
+	fn release_fund() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: VanePayment Resolvers (r:1 w:1)
+	fn add_resolver() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: VanePayment Resolvers (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: VanePayment ResolverSigner (r:1 w:1)
+	fn bond_resolver() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: VanePayment ResolverAssignments (r:1 w:0)
+	// Storage: VanePayment Resolvers (r:1 w:1)
+	// Storage: VanePayment ResolverSigner (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove_resolver() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:0)
+	// Storage: VanePayment SplitPayees (r:1 w:0)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: VanePayment Resolvers (r:1 w:0)
+	// Storage: VanePayment ResolverSigner (r:1 w:0)
+	// Storage: VanePayment NextResolver (r:1 w:1)
+	// Storage: VanePayment RulingDeadlines (r:1 w:1)
+	// Storage: VanePayment ResolverAssignments (r:1 w:1)
+	// Storage: VanePayment DisputeResolvers (r:0 w:1)
+	fn open_dispute() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:0)
+	// Storage: VanePayment SplitPayees (r:1 w:0)
+	// Storage: VanePayment Disputes (r:1 w:1)
+	fn add_evidence() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment Rulings (r:1 w:1)
+	// Storage: VanePayment Resolvers (r:1 w:1)
+	// Storage: VanePayment ResolverFees (r:1 w:1)
+	// Storage: VanePayment DisputeResolvers (r:0 w:1)
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment LockedAmount (r:3 w:3)
+	// Storage: VanePayment EscrowedTotal (r:2 w:2)
+	// Storage: System Account (r:6 w:6)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment SplitConfirmations (r:1 w:1)
+	// Storage: VanePayment PayerHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayerHistory (r:0 w:2)
+	// Storage: VanePayment PayeeHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayeeHistory (r:0 w:2)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	fn resolve_dispute() -> Weight {
+		(290_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(30 as Weight))
+	}
+	// Storage: VanePayment AllowedSigners (r:1 w:0)
+	// Storage: VanePayment SplitPayees (r:1 w:0)
+	// Storage: VanePayment Rulings (r:1 w:1)
+	fn appeal_ruling() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: VanePayment Disputes (r:1 w:1)
+	// Storage: VanePayment Rulings (r:1 w:1)
+	// Storage: VanePayment ResolverFees (r:1 w:1)
+	// Storage: VanePayment DisputeResolvers (r:0 w:1)
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment LockedAmount (r:3 w:3)
+	// Storage: VanePayment EscrowedTotal (r:2 w:2)
+	// Storage: System Account (r:6 w:6)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment SplitConfirmations (r:1 w:1)
+	// Storage: VanePayment PayerHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayerHistory (r:0 w:2)
+	// Storage: VanePayment PayeeHistoryBounds (r:1 w:1)
+	// Storage: VanePayment PayeeHistory (r:0 w:2)
+	// Storage: VanePayment ConfirmedSigners (r:0 w:1)
+	// Storage: VanePayment RevertRequests (r:0 w:1)
+	// Storage: VanePayment PaymentExpiry (r:0 w:1)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	// Storage: VanePayment Milestones (r:0 w:1)
+	fn execute_ruling() -> Weight {
+		(281_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(20 as Weight))
+			.saturating_add(T::DbWeight::get().writes(29 as Weight))
+	}
+	// Storage: VanePayment ExpiringPayments (r:1 w:1)
+	// Storage: VanePayment PaymentExpiry (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:0 w:1)
+	// Storage: VanePayment AllowedSigners (r:1 w:1)
+	// Storage: VanePayment ConfirmedSigners (r:1 w:1)
+	// Storage: VanePayment HashLocks (r:1 w:1)
+	// Storage: VanePayment ClaimCommitments (r:1 w:1)
+	// Storage: VanePayment Milestones (r:1 w:1)
+	// Storage: VanePayment SplitPayees (r:1 w:1)
+	// Storage: VanePayment SplitConfirmations (r:1 w:1)
+	// Storage: VanePayment LockedAmount (r:1 w:1)
+	// Storage: VanePayment EscrowedTotal (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: VanePayment PaymentDeposits (r:1 w:1)
+	// Storage: VanePayment FaultDecay (r:2 w:1)
+	// Storage: VanePayment RevertedTxnPayee (r:1 w:1)
+	fn refund_expired_payments(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((142_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((17 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((22 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: VanePayment FaultDecay (r:1 w:1)
+	// Storage: VanePayment RevertedTxnPayer (r:1 w:1)
+	// Storage: VanePayment RevertedTxnPayee (r:1 w:0)
+	fn decay_faults(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: VanePayment RulingDeadlines (r:2 w:2)
+	// Storage: VanePayment DisputeResolvers (r:1 w:1)
+	// Storage: VanePayment AllowedSigners (r:1 w:0)
+	// Storage: VanePayment SplitPayees (r:1 w:0)
+	// Storage: VanePayment ResolverSigner (r:1 w:0)
+	// Storage: VanePayment NextResolver (r:1 w:1)
+	// Storage: VanePayment ResolverAssignments (r:2 w:2)
+	fn replace_late_resolvers(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((31_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: VanePayment NextPrunedBlock (r:1 w:1)
+	// Storage: VanePayment ClosedPayments (r:1 w:1)
+	// Storage: VanePayment PaymentStatuses (r:0 w:1)
+	// Storage: VanePayment RejectionReasons (r:0 w:1)
+	fn prune_closed_payments(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn vane_pay() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	fn vane_pay_split(p: u32, ) -> Weight {
+		(101_000_000 as Weight)
+			.saturating_add((310_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	fn vane_pay_milestones(m: u32, ) -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add((240_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	fn vane_pay_htlc() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn claim_htlc() -> Weight {
+		(224_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(25 as Weight))
+	}
+	fn reclaim_htlc() -> Weight {
+		(82_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+	}
+	fn claim_payment() -> Weight {
+		(221_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(26 as Weight))
+	}
+	fn confirm_pay(p: u32, ) -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add((38_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(22 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn confirm_milestone() -> Weight {
+		(131_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(24 as Weight))
+	}
+	fn revert_fund() -> Weight {
+		(121_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+	}
+	fn cancel_payment() -> Weight {
+		(84_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn reject_payment() -> Weight {
+		(83_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+	}
+	fn propose_refund() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn accept_refund() -> Weight {
+		(243_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(30 as Weight))
+	}
+	fn release_fund() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn add_resolver() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn bond_resolver() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn remove_resolver() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn open_dispute() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn add_evidence() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resolve_dispute() -> Weight {
+		(290_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(30 as Weight))
+	}
+	fn appeal_ruling() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn execute_ruling() -> Weight {
+		(281_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(20 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(29 as Weight))
+	}
+	fn refund_expired_payments(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((142_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((17 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((22 as Weight).saturating_mul(n as Weight)))
+	}
+	fn decay_faults(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn replace_late_resolvers(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((31_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((8 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn prune_closed_payments(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+}
@@ -33,7 +33,7 @@ pub use frame_support::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
@@ -44,6 +44,7 @@ pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 
 pub use pallet_multisig_payment;
+use pallet_multisig_payment_runtime_api::HistoryPage;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -267,9 +268,113 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	pub const SpendPeriod: BlockNumber = DAYS;
+	pub const Burn: Permill = Permill::zero();
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxApprovals: u32 = 100;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+	type RejectOrigin = frame_system::EnsureRoot<AccountId>;
+	type Event = Event;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	pub const AssetAccountDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const MetadataDepositBase: Balance = 10 * EXISTENTIAL_DEPOSIT;
+	pub const MetadataDepositPerByte: Balance = EXISTENTIAL_DEPOSIT;
+	pub const ApprovalDeposit: Balance = EXISTENTIAL_DEPOSIT;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	// Taken from every settled Vane payment and sent to the treasury
+	pub const VaneProtocolFee: Perbill = Perbill::from_parts(5_000_000);
+	pub const VanePenaltyDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	// Reserved from whoever opens a Vane dispute
+	pub const VaneDisputeDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	pub const VaneFaultPenalty: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+	// Legal teams bond this to resolve Vane disputes, charge at most the fee share and lose the
+	// slash share of the bond when governance overturns their ruling
+	pub const VaneResolverBond: Balance = 10_000 * EXISTENTIAL_DEPOSIT;
+	pub const VaneMaxResolverFee: Perbill = Perbill::from_percent(5);
+	pub const VaneResolverSlash: Perbill = Perbill::from_percent(20);
+	// The protocol fee of payments made in assets goes to the treasury account
+	pub VaneAssetFeeDestination: AccountId = Treasury::account_id();
+}
+
 impl pallet_multisig_payment::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
+	type Escrow = pallet_multisig_payment::MultiIdEscrow;
+	type ProtocolFee = VaneProtocolFee;
+	type FeeDestination = Treasury;
+	type Assets = Assets;
+	type AssetFeeDestination = VaneAssetFeeDestination;
+	type PaymentTimeout = ConstU32<{ 7 * DAYS }>;
+	type MaxExpiringPerBlock = ConstU32<100>;
+	// Root, i.e. the sudo key, stands in for governance until the runtime has a council. Legal
+	// teams are admitted and disputes decided by the sudo key alone in the meantime.
+	type ResolverOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxResolvers = ConstU32<50>;
+	type ResolverBond = VaneResolverBond;
+	type MaxResolverFee = VaneMaxResolverFee;
+	type ResolverSlash = VaneResolverSlash;
+	type RulingPeriod = ConstU32<{ 3 * DAYS }>;
+	type AppealPeriod = ConstU32<{ 2 * DAYS }>;
+	// Temporary stand-in as well, to become a council motion
+	type DisputeOrigin = frame_system::EnsureRoot<AccountId>;
+	type DisputeDeposit = VaneDisputeDeposit;
+	type MaxEvidence = ConstU32<16>;
+	type FaultWindow = ConstU32<{ 30 * DAYS }>;
+	type MaxFaultsPerBlock = ConstU32<100>;
+	type DepositThreshold = ConstU32<3>;
+	type PenaltyDeposit = VanePenaltyDeposit;
+	type BarThreshold = ConstU32<5>;
+	type BarDuration = ConstU32<{ 7 * DAYS }>;
+	type SlashThreshold = ConstU32<10>;
+	type FaultPenalty = VaneFaultPenalty;
+	type MaxPayees = ConstU32<10>;
+	type MaxMilestones = ConstU32<20>;
+	type MaxReasonLength = ConstU32<32>;
+	type MaxPreimageLength = ConstU32<64>;
+	type MaxHistoryPerAccount = ConstU32<500>;
+	type StatusRetention = ConstU32<{ 30 * DAYS }>;
+	type MaxClosedPerBlock = ConstU32<1000>;
+	type WeightInfo = pallet_multisig_payment::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -294,6 +399,8 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
 		VanePayment: pallet_multisig_payment,
+		Treasury: pallet_treasury,
+		Assets: pallet_assets,
 	}
 );
 
@@ -338,7 +445,7 @@ mod benches {
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
-		[pallet_multisig_payment, MultisigPayment]
+		[pallet_multisig_payment, VanePayment]
 	);
 }
 
@@ -458,6 +565,30 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_multisig_payment_runtime_api::VanePaymentApi<
+		Block,
+		AccountId,
+		pallet_multisig_payment::CallExecuted<Runtime>,
+	> for Runtime {
+		fn payer_history(
+			who: AccountId,
+			start: u32,
+			limit: u32,
+		) -> HistoryPage<pallet_multisig_payment::CallExecuted<Runtime>> {
+			let (records, next) = VanePayment::payer_history_page(&who, start, limit);
+			HistoryPage { records, next }
+		}
+
+		fn payee_history(
+			who: AccountId,
+			start: u32,
+			limit: u32,
+		) -> HistoryPage<pallet_multisig_payment::CallExecuted<Runtime>> {
+			let (records, next) = VanePayment::payee_history_page(&who, start, limit);
+			HistoryPage { records, next }
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,